@@ -0,0 +1,182 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::AocError;
+use crate::registry::get_registration;
+use crate::report::{write_csv_records, write_json_records};
+
+/// Minimum, median and maximum of a set of measurements
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    /// Summarises the samples, which must not be empty
+    pub fn from_samples(samples: &mut [Duration]) -> Timings {
+        samples.sort_unstable();
+        Timings {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of parsing the input and solving one part, measured separately
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    pub parse: Timings,
+    pub solve: Timings,
+}
+
+/// Flat representation of a `BenchResult` for JSON and CSV files. Durations are given in nanoseconds
+#[derive(Debug, Serialize)]
+struct BenchRecord {
+    day: u8,
+    part: u8,
+    runs: usize,
+    parse_min_ns: u64,
+    parse_median_ns: u64,
+    parse_max_ns: u64,
+    solve_min_ns: u64,
+    solve_median_ns: u64,
+    solve_max_ns: u64,
+}
+
+impl From<&BenchResult> for BenchRecord {
+    fn from(result: &BenchResult) -> Self {
+        BenchRecord {
+            day: result.day,
+            part: result.part,
+            runs: result.runs,
+            parse_min_ns: result.parse.min.as_nanos() as u64,
+            parse_median_ns: result.parse.median.as_nanos() as u64,
+            parse_max_ns: result.parse.max.as_nanos() as u64,
+            solve_min_ns: result.solve.min.as_nanos() as u64,
+            solve_median_ns: result.solve.median.as_nanos() as u64,
+            solve_max_ns: result.solve.max.as_nanos() as u64,
+        }
+    }
+}
+
+/// Parses the input and solves the part `runs` times
+pub fn bench(day: u8, part: u8, input: &str, runs: usize) -> Result<BenchResult, AocError> {
+    let registration = get_registration(day).ok_or(AocError::UnknownDay { day })?;
+    if !registration.parts.contains(&part) {
+        return Err(AocError::UnknownPart { day, part });
+    }
+    let runs = runs.max(1);
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        let solver = registration.parse(input)?;
+        parse_samples.push(start.elapsed());
+        let start = Instant::now();
+        solver.solve(part)?;
+        solve_samples.push(start.elapsed());
+    }
+    Ok(BenchResult {
+        day,
+        part,
+        runs,
+        parse: Timings::from_samples(&mut parse_samples),
+        solve: Timings::from_samples(&mut solve_samples),
+    })
+}
+
+pub fn write_json(results: &[BenchResult], writer: impl Write) -> Result<(), String> {
+    let records: Vec<BenchRecord> = results.iter().map(BenchRecord::from).collect();
+    write_json_records(&records, writer)
+}
+
+pub fn write_csv(results: &[BenchResult], writer: impl Write) -> Result<(), String> {
+    let records: Vec<BenchRecord> = results.iter().map(BenchRecord::from).collect();
+    write_csv_records(&records, writer)
+}
+
+/// Format of a results file
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Determines the format from the extension of `path`
+    pub fn from_path(path: &Path) -> Result<OutputFormat, String> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(OutputFormat::Json),
+            Some("csv") => Ok(OutputFormat::Csv),
+            _ => Err(format!("Unsupported file type of {} (expected .json or .csv)", path.display())),
+        }
+    }
+}
+
+/// Writes the results to a JSON or CSV file, depending on the extension of `path`
+pub fn write_results(results: &[BenchResult], path: &Path) -> Result<(), String> {
+    let format = OutputFormat::from_path(path)?;
+    let file = std::fs::File::create(path).map_err(|e| format!("Error writing {}: {}", path.display(), e))?;
+    match format {
+        OutputFormat::Json => write_json(results, file),
+        OutputFormat::Csv => write_csv(results, file),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_result() -> BenchResult {
+        let timings = Timings { min: Duration::from_nanos(1), median: Duration::from_nanos(2), max: Duration::from_nanos(3) };
+        BenchResult { day: 1, part: 2, runs: 3, parse: timings, solve: timings }
+    }
+
+    #[test]
+    fn test_timings() {
+        let mut samples = vec![Duration::from_millis(5), Duration::from_millis(1), Duration::from_millis(3)];
+        let expected = Timings { min: Duration::from_millis(1), median: Duration::from_millis(3), max: Duration::from_millis(5) };
+        assert_eq!(expected, Timings::from_samples(&mut samples));
+    }
+
+    #[test]
+    fn test_bench() {
+        let result = bench(1, 1, "1721\n979\n366\n299\n675\n1456", 3).unwrap();
+        assert_eq!((1, 1, 3), (result.day, result.part, result.runs));
+        assert!(result.solve.min <= result.solve.median && result.solve.median <= result.solve.max);
+        assert_eq!(Err(AocError::UnknownPart { day: 25, part: 2 }), bench(25, 2, "", 1));
+    }
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!(Ok(OutputFormat::Json), OutputFormat::from_path(Path::new("bench/results.json")));
+        assert_eq!(Ok(OutputFormat::Csv), OutputFormat::from_path(Path::new("results.csv")));
+        assert!(OutputFormat::from_path(Path::new("results.txt")).is_err());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut output = Vec::new();
+        write_csv(&[get_result()], &mut output).unwrap();
+        assert_eq!("day,part,runs,parse_min_ns,parse_median_ns,parse_max_ns,solve_min_ns,solve_median_ns,solve_max_ns\n\
+                    1,2,3,1,2,3,1,2,3\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut output = Vec::new();
+        write_json(&[get_result()], &mut output).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(2, json[0]["part"]);
+        assert_eq!(2, json[0]["solve_median_ns"]);
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use aoc2020::bench::OutputFormat;
+use aoc2020::manifest::DEFAULT_MANIFEST;
+use aoc2020::report::ReportFormat;
+
+pub const USAGE: &str = r"Usage:
+    aoc2020 run --day <DAY> [--part <PART>] [--input <FILE>] [--format <FORMAT>]
+    aoc2020 run --all [--part <PART>] [--format <FORMAT>] [--jobs <JOBS>]
+    aoc2020 verify [--day <DAY>] [--part <PART>] [--manifest <FILE>]
+    aoc2020 bench (--day <DAY> | --all) [--part <PART>] [--runs <RUNS>] [--output <FILE>]
+    aoc2020 help
+
+Options:
+    --day <DAY>         Day to solve (1-25)
+    --all               Solve all days
+    --part <PART>       Only solve the given part (1 or 2)
+    --input <FILE>      Read the puzzle input from FILE, or from stdin if FILE is '-'.
+                        Defaults to resources/day<DAY>.txt
+    --format <FORMAT>   Print the results as text (default), json or csv
+    --jobs <JOBS>       Number of days solved in parallel (default 1)
+    --manifest <FILE>   Check against the expected answers listed in FILE.
+                        Defaults to resources/answers.txt
+    --runs <RUNS>       Number of times each part is parsed and solved (default 10)
+    --output <FILE>     Write the timings to FILE, as JSON or CSV depending on its extension";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    pub input: Option<Input>,
+    pub format: ReportFormat,
+    pub jobs: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub manifest: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    pub runs: usize,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Command {
+    pub fn from_args(args: &[String]) -> Result<Command, String> {
+        let mut args = args.iter().map(|arg| arg.as_str());
+        match args.next() {
+            None | Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+            Some("run") => Ok(Command::Run(RunOptions::from_args(args)?)),
+            Some("verify") => Ok(Command::Verify(VerifyOptions::from_args(args)?)),
+            Some("bench") => Ok(Command::Bench(BenchOptions::from_args(args)?)),
+            Some(other) => Err(format!("Unknown command: '{}'", other)),
+        }
+    }
+}
+
+impl RunOptions {
+    fn from_args<'a>(mut args: impl Iterator<Item=&'a str>) -> Result<RunOptions, String> {
+        let mut day: Option<u8> = None;
+        let mut all = false;
+        let mut part: Option<u8> = None;
+        let mut input: Option<Input> = None;
+        let mut format = ReportFormat::Text;
+        let mut jobs = 1;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => {
+                    let value = args.next().ok_or("--day requires a value")?;
+                    day = Some(parse_number(value, 1..=25, "day")?);
+                }
+                "--part" | "-p" => {
+                    let value = args.next().ok_or("--part requires a value")?;
+                    part = Some(parse_number(value, 1..=2, "part")?);
+                }
+                "--input" | "-i" => {
+                    input = Some(match args.next().ok_or("--input requires a value")? {
+                        "-" => Input::Stdin,
+                        path => Input::File(PathBuf::from(path)),
+                    });
+                }
+                "--format" | "-f" => {
+                    format = args.next().ok_or("--format requires a value")?.parse()?;
+                }
+                "--jobs" | "-j" => {
+                    let value = args.next().ok_or("--jobs requires a value")?;
+                    jobs = parse_number(value, 1..=usize::MAX, "number of jobs")?;
+                }
+                "--all" | "-a" => all = true,
+                _ => return Err(format!("Unknown argument: '{}'", arg)),
+            }
+        }
+        if all && input.is_some() {
+            return Err(String::from("--input can only be used together with --day"));
+        }
+        Ok(RunOptions { days: select_days(day, all)?, part, input, format, jobs })
+    }
+}
+
+impl VerifyOptions {
+    fn from_args<'a>(mut args: impl Iterator<Item=&'a str>) -> Result<VerifyOptions, String> {
+        let mut options = VerifyOptions { day: None, part: None, manifest: PathBuf::from(DEFAULT_MANIFEST) };
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => {
+                    let value = args.next().ok_or("--day requires a value")?;
+                    options.day = Some(parse_number(value, 1..=25, "day")?);
+                }
+                "--part" | "-p" => {
+                    let value = args.next().ok_or("--part requires a value")?;
+                    options.part = Some(parse_number(value, 1..=2, "part")?);
+                }
+                "--manifest" | "-m" => {
+                    options.manifest = PathBuf::from(args.next().ok_or("--manifest requires a value")?);
+                }
+                _ => return Err(format!("Unknown argument: '{}'", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl BenchOptions {
+    fn from_args<'a>(mut args: impl Iterator<Item=&'a str>) -> Result<BenchOptions, String> {
+        let mut day: Option<u8> = None;
+        let mut all = false;
+        let mut part: Option<u8> = None;
+        let mut runs = 10;
+        let mut output: Option<PathBuf> = None;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => {
+                    let value = args.next().ok_or("--day requires a value")?;
+                    day = Some(parse_number(value, 1..=25, "day")?);
+                }
+                "--part" | "-p" => {
+                    let value = args.next().ok_or("--part requires a value")?;
+                    part = Some(parse_number(value, 1..=2, "part")?);
+                }
+                "--runs" | "-r" => {
+                    let value = args.next().ok_or("--runs requires a value")?;
+                    runs = parse_number(value, 1..=usize::MAX, "number of runs")?;
+                }
+                "--output" | "-o" => {
+                    output = Some(PathBuf::from(args.next().ok_or("--output requires a value")?));
+                }
+                "--all" | "-a" => all = true,
+                _ => return Err(format!("Unknown argument: '{}'", arg)),
+            }
+        }
+        if let Some(output) = &output {
+            OutputFormat::from_path(output)?;
+        }
+        Ok(BenchOptions { days: select_days(day, all)?, part, runs, output })
+    }
+}
+
+fn select_days(day: Option<u8>, all: bool) -> Result<Vec<u8>, String> {
+    match (day, all) {
+        (Some(_), true) => Err(String::from("--day and --all cannot be combined")),
+        (None, false) => Err(String::from("Either --day or --all is required")),
+        (Some(day), false) => Ok(vec![day]),
+        (None, true) => Ok((1..=25).collect()),
+    }
+}
+
+fn parse_number<T>(value: &str, range: RangeInclusive<T>, name: &str) -> Result<T, String>
+    where T: FromStr + PartialOrd + fmt::Display {
+    match value.parse::<T>() {
+        Ok(number) if range.contains(&number) => Ok(number),
+        _ => Err(format!("Invalid {}: '{}' (expected {} to {})", name, value, range.start(), range.end())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_run_single_day() {
+        let expected = RunOptions {
+            days: vec![14],
+            part: Some(2),
+            input: Some(Input::File(PathBuf::from("foo.txt"))),
+            format: ReportFormat::Text,
+            jobs: 1,
+        };
+        assert_eq!(Ok(Command::Run(expected)), Command::from_args(&args("run --day 14 --part 2 --input foo.txt")));
+    }
+
+    #[test]
+    fn test_run_stdin() {
+        let expected = RunOptions {
+            days: vec![3],
+            part: None,
+            input: Some(Input::Stdin),
+            format: ReportFormat::Json,
+            jobs: 1,
+        };
+        assert_eq!(Ok(Command::Run(expected)), Command::from_args(&args("run -d 3 -i - --format json")));
+    }
+
+    #[test]
+    fn test_run_all() {
+        match Command::from_args(&args("run --all --part 1 --jobs 4")).unwrap() {
+            Command::Run(options) => {
+                assert_eq!((1..=25).collect::<Vec<u8>>(), options.days);
+                assert_eq!(Some(1), options.part);
+                assert_eq!(4, options.jobs);
+            }
+            _ => panic!("expected run command"),
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let expected = VerifyOptions {
+            day: None,
+            part: None,
+            manifest: PathBuf::from("resources/answers.txt"),
+        };
+        assert_eq!(Ok(Command::Verify(expected)), Command::from_args(&args("verify")));
+        let expected = VerifyOptions {
+            day: Some(8),
+            part: Some(2),
+            manifest: PathBuf::from("answers.txt"),
+        };
+        assert_eq!(Ok(Command::Verify(expected)), Command::from_args(&args("verify --day 8 -p 2 --manifest answers.txt")));
+        assert!(Command::from_args(&args("verify --all")).is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let expected = BenchOptions {
+            days: vec![15],
+            part: Some(2),
+            runs: 3,
+            output: Some(PathBuf::from("bench.json")),
+        };
+        assert_eq!(Ok(Command::Bench(expected)), Command::from_args(&args("bench --day 15 --part 2 --runs 3 --output bench.json")));
+        match Command::from_args(&args("bench --all")).unwrap() {
+            Command::Bench(options) => {
+                assert_eq!(25, options.days.len());
+                assert_eq!(10, options.runs);
+                assert_eq!(None, options.output);
+            }
+            _ => panic!("expected bench command"),
+        }
+        assert!(Command::from_args(&args("bench --day 1 --runs 0")).is_err());
+        assert!(Command::from_args(&args("bench")).is_err());
+        assert!(Command::from_args(&args("bench --all --output bench.txt")).is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        assert_eq!(Ok(Command::Help), Command::from_args(&[]));
+        assert_eq!(Ok(Command::Help), Command::from_args(&args("help")));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(Command::from_args(&args("fly")).is_err());
+        assert!(Command::from_args(&args("run")).is_err());
+        assert!(Command::from_args(&args("run --day 26")).is_err());
+        assert!(Command::from_args(&args("run --day 1 --part 3")).is_err());
+        assert!(Command::from_args(&args("run --day 1 --all")).is_err());
+        assert!(Command::from_args(&args("run --all --input foo.txt")).is_err());
+        assert!(Command::from_args(&args("run --day")).is_err());
+        assert!(Command::from_args(&args("run --day 1 --verbose")).is_err());
+        assert!(Command::from_args(&args("run --day 1 --format xml")).is_err());
+        assert!(Command::from_args(&args("run --all --jobs 0")).is_err());
+    }
+}
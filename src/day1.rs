@@ -1,39 +1,193 @@
-pub fn day1a(input: &str) -> Option<usize> {
-    let expenses: Vec<usize> = input
-        .trim()
-        .split('\n')
-        .map(|x| {
-            x.parse().unwrap()
-        })
-        .collect();
-    for x in &expenses {
-        for y in &expenses {
-            if x + y == 2020 {
-                return Some(x * y);
+use std::collections::BTreeMap;
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 1;
+
+/// The expenses have to sum up to this value
+const TARGET: usize = 2020;
+
+/// Parses the expense report, one entry per line
+pub fn parse_expenses(input: &str) -> Result<Vec<usize>, AocError> {
+    lines(input)
+        .map(|x| parse_fragment(DAY, input, x))
+        .collect()
+}
+
+/// Finds `k` different entries of `expenses` that sum up to `target` and returns them in ascending order.
+/// Each entry is used at most once, but equal values in different entries can be combined.
+/// Takes O(n log n) for up to two entries and O(n^(k-1)) for more
+pub fn find_entries(expenses: &[usize], target: usize, k: usize) -> Option<Vec<usize>> {
+    let mut sorted = expenses.to_vec();
+    sorted.sort_unstable();
+    find_sorted_entries(&sorted, target, k)
+}
+
+fn find_sorted_entries(sorted: &[usize], target: usize, k: usize) -> Option<Vec<usize>> {
+    match k {
+        0 => if target == 0 { Some(Vec::new()) } else { None },
+        1 => sorted.binary_search(&target).ok().map(|_| vec![target]),
+        2 => {
+            let (mut low, mut high) = (0, sorted.len().checked_sub(1)?);
+            while low < high {
+                match sorted[low].checked_add(sorted[high]) {
+                    Some(sum) if sum == target => return Some(vec![sorted[low], sorted[high]]),
+                    Some(sum) if sum < target => low += 1,
+                    _ => high -= 1,
+                }
             }
+            None
+        }
+        _ => {
+            for (idx, &entry) in sorted.iter().enumerate() {
+                // all other entries are at least as large as this one
+                if entry.checked_mul(k).filter(|&minimum| minimum <= target).is_none() {
+                    break;
+                }
+                if idx > 0 && sorted[idx - 1] == entry {
+                    continue;
+                }
+                if let Some(mut entries) = find_sorted_entries(&sorted[idx + 1..], target - entry, k - 1) {
+                    entries.insert(0, entry);
+                    return Some(entries);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Iterator over all combinations of `k` different entries that sum up to a target. Each combination is given by
+/// the indices of its entries in ascending order. Entries with equal values are told apart by their index, so they
+/// can lead to several combinations with the same values
+pub struct Solutions {
+    /// Values and indices of the entries, sorted by value
+    sorted: Vec<(usize, usize)>,
+    target: usize,
+    k: usize,
+    /// Position in `sorted` of the candidate for each level of the search; the last one is examined next
+    stack: Vec<usize>,
+    /// Sum of the entries chosen on the levels before each level
+    sums: Vec<usize>,
+    /// Whether the empty combination is still to be returned, which is the only solution for k = 0
+    empty_solution: bool,
+}
+
+impl Solutions {
+    pub fn new(expenses: &[usize], target: usize, k: usize) -> Solutions {
+        let mut sorted: Vec<(usize, usize)> = expenses.iter().copied().zip(0..).collect();
+        sorted.sort_unstable();
+        let (stack, sums) = if k > 0 { (vec![0], vec![0]) } else { (Vec::new(), Vec::new()) };
+        Solutions { sorted, target, k, stack, sums, empty_solution: k == 0 && target == 0 }
+    }
+
+    /// Abandons the current level and moves on to the next candidate of the level before
+    fn backtrack(&mut self) {
+        self.stack.pop();
+        self.sums.pop();
+        if let Some(position) = self.stack.last_mut() {
+            *position += 1;
         }
     }
-    None
 }
 
-pub fn day1b(input: &str) -> Option<usize> {
-    let expenses: Vec<usize> = input
-        .trim()
-        .split('\n')
-        .map(|x| {
-            x.parse().unwrap()
-        })
-        .collect();
-    for x in expenses.iter() {
-        for y in expenses.iter() {
-            for z in expenses.iter() {
-                if x + y + z == 2020 {
-                    return Some(x * y * z);
+impl Iterator for Solutions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.empty_solution {
+            self.empty_solution = false;
+            return Some(Vec::new());
+        }
+        while let Some(&position) = self.stack.last() {
+            let level = self.stack.len() - 1;
+            let budget = self.target - self.sums[level];
+            let value = match self.sorted.get(position) {
+                Some(&(value, _)) => value,
+                None => {
+                    self.backtrack();
+                    continue;
                 }
+            };
+            // the remaining entries of this combination are at least as large as this one
+            if value.checked_mul(self.k - level).filter(|&minimum| minimum <= budget).is_none() {
+                self.backtrack();
+                continue;
+            }
+            if level + 1 < self.k {
+                self.sums.push(self.sums[level] + value);
+                self.stack.push(position + 1);
+                continue;
             }
+            if value == budget {
+                let mut indices: Vec<usize> = self.stack.iter().map(|&position| self.sorted[position].1).collect();
+                indices.sort_unstable();
+                *self.stack.last_mut().unwrap() += 1;
+                return Some(indices);
+            }
+            // skip the entries that are too small to complete the combination
+            let skipped = self.sorted[position..].partition_point(|&(value, _)| value < budget);
+            *self.stack.last_mut().unwrap() += skipped;
         }
+        None
+    }
+}
+
+/// Counts the combinations of `k` different entries that sum up to `target`, grouped by their values in ascending
+/// order. More than one group, or a count above one, means that the answer is ambiguous
+pub fn count_solutions(expenses: &[usize], target: usize, k: usize) -> BTreeMap<Vec<usize>, usize> {
+    let mut counts = BTreeMap::new();
+    for indices in Solutions::new(expenses, target, k) {
+        let mut values: Vec<usize> = indices.iter().map(|&idx| expenses[idx]).collect();
+        values.sort_unstable();
+        *counts.entry(values).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn get_product(expenses: &[usize], k: usize, error: &str) -> Result<usize, AocError> {
+    let entries = find_entries(expenses, TARGET, k).ok_or_else(|| AocError::no_solution(DAY, error))?;
+    entries.iter().try_fold(1usize, |product, &entry| product.checked_mul(entry))
+        .ok_or_else(|| AocError::no_solution(DAY, "The product overflows"))
+}
+
+fn get_product_of_pair(expenses: &[usize]) -> Result<usize, AocError> {
+    get_product(expenses, 2, "No two expenses sum up to 2020")
+}
+
+fn get_product_of_triple(expenses: &[usize]) -> Result<usize, AocError> {
+    get_product(expenses, 3, "No three expenses sum up to 2020")
+}
+
+pub fn day1a(input: &str) -> Result<usize, AocError> {
+    get_product_of_pair(&parse_expenses(input)?)
+}
+
+pub fn day1b(input: &str) -> Result<usize, AocError> {
+    get_product_of_triple(&parse_expenses(input)?)
+}
+
+pub struct Day1 {
+    expenses: Vec<usize>,
+}
+
+impl Solver for Day1 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day1 { expenses: parse_expenses(input)? })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_product_of_pair(&self.expenses)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_product_of_triple(&self.expenses)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
     }
-    None
 }
 
 #[cfg(test)]
@@ -65,4 +219,57 @@ mod tests {
             .expect("Error reading file to string");
         println!("part b: {}", day1b(&*input).unwrap());
     }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err(AocError::parse(1, 2, 1, "Invalid value 'x979': invalid digit found in string")),
+                   day1a("1721\nx979\n366"));
+        assert_eq!(Err(AocError::no_solution(1, "No two expenses sum up to 2020")), day1a("1\n2\n3"));
+        assert_eq!(Err(AocError::no_solution(1, "No two expenses sum up to 2020")), day1a("1010\n3"));
+    }
+
+    #[test]
+    fn test_find_entries() {
+        let expenses = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(Some(vec![299, 1721]), find_entries(&expenses, 2020, 2));
+        assert_eq!(Some(vec![366, 675, 979]), find_entries(&expenses, 2020, 3));
+        assert_eq!(Some(vec![299, 366, 675, 979, 1721]), find_entries(&expenses, 4040, 5));
+        assert_eq!(Some(vec![979]), find_entries(&expenses, 979, 1));
+        assert_eq!(Some(vec![]), find_entries(&expenses, 0, 0));
+        assert_eq!(None, find_entries(&expenses, 2020, 7));
+        assert_eq!(None, find_entries(&[1010, 5], 2020, 2));
+        assert_eq!(Some(vec![1010, 1010]), find_entries(&[1010, 5, 1010], 2020, 2));
+        assert_eq!(None, find_entries(&[usize::MAX, 1, 2], 3, 3));
+    }
+
+    #[test]
+    fn test_solutions() {
+        let expenses = parse_expenses("1721\n979\n366\n299\n675\n1456").unwrap();
+        assert_eq!(vec![vec![0, 3]], Solutions::new(&expenses, 2020, 2).collect::<Vec<_>>());
+        assert_eq!(vec![vec![1, 2, 4]], Solutions::new(&expenses, 2020, 3).collect::<Vec<_>>());
+        assert_eq!(vec![vec![0, 3], vec![1, 2]], Solutions::new(&[1, 2, 3, 4, 5], 5, 2).collect::<Vec<_>>());
+        assert_eq!(vec![vec![0, 1, 3], vec![0, 2, 3]], Solutions::new(&[1, 2, 2, 3], 6, 3).collect::<Vec<_>>());
+        assert_eq!(vec![Vec::<usize>::new()], Solutions::new(&expenses, 0, 0).collect::<Vec<_>>());
+        assert_eq!(0, Solutions::new(&expenses, 2020, 7).count());
+        assert_eq!(0, Solutions::new(&[], 2020, 2).count());
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let expenses = [1010, 1000, 1010, 1020, 1010];
+        let mut expected = BTreeMap::new();
+        expected.insert(vec![1000, 1020], 1);
+        expected.insert(vec![1010, 1010], 3);
+        assert_eq!(expected, count_solutions(&expenses, 2020, 2));
+        assert!(count_solutions(&expenses, 1, 2).is_empty());
+    }
+
+    #[test]
+    fn test_find_entries_in_long_report() {
+        let expenses: Vec<usize> = (0..50_000).map(|i| 3 * i + 1).collect();
+        let entries = find_entries(&expenses, 100_001, 2).unwrap();
+        assert_eq!(100_001, entries.iter().sum::<usize>());
+        let entries = find_entries(&expenses, 3003, 3).unwrap();
+        assert_eq!(3003, entries.iter().sum::<usize>());
+    }
 }
@@ -1,6 +1,12 @@
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 10;
+
 /// returns the number of 1-jolt differences multiplied by the number of 3-jolt differences
-pub fn get_jolt_difference(input: &str) -> usize {
-    let vec: Vec<u8> = parse_input(input);
+pub fn get_jolt_difference(input: &str) -> Result<usize, AocError> {
+    let vec: Vec<u8> = parse_input(input)?;
     let mut one_difference = 0;
     let mut three_difference = 0; // already one because of the hop to the device
     let mut previous_joltage: u8 = 0;
@@ -14,19 +20,19 @@ pub fn get_jolt_difference(input: &str) -> usize {
         previous_joltage = *current_joltage;
     }
 
-    one_difference * three_difference
+    Ok(one_difference * three_difference)
 }
 
-pub fn get_number_of_possible_arrangements(input: &str) -> u64 {
-    let adapters: Vec<u8> = parse_input(input);
+pub fn get_number_of_possible_arrangements(input: &str) -> Result<u64, AocError> {
+    let adapters: Vec<u8> = parse_input(input)?;
     let clusters = get_clusters(&adapters);
-    clusters.iter().map(|(start, stop)| {
+    Ok(clusters.iter().map(|(start, stop)| {
         if *start == 0 {
             return get_number_of_possible_arrangements_recursive(&adapters[*start..stop + 1], 0);
         }
         get_number_of_possible_arrangements_recursive(&adapters[start - 1..stop + 1], 0)
     })
-        .product()
+        .product())
 }
 
 fn get_number_of_possible_arrangements_recursive(adapters: &[u8], current_index: usize) -> u64 {//, possibilities: &mut Box<u64>) {
@@ -49,15 +55,16 @@ fn get_number_of_possible_arrangements_recursive(adapters: &[u8], current_index:
     num_arrangements
 }
 
-fn parse_input(input: &str) -> Vec<u8> {
-    let mut vec: Vec<u8> = input.trim()
-        .split('\n')
-        .map(|x| x.parse().unwrap())
-        .collect();
+fn parse_input(input: &str) -> Result<Vec<u8>, AocError> {
+    let mut vec: Vec<u8> = lines(input)
+        .map(|x| parse_fragment(DAY, input, x))
+        .collect::<Result<_, _>>()?;
     vec.sort_unstable();
     vec.insert(0, 0);
-    vec.push(vec.last().unwrap() + 3);
-    vec
+    let device = vec.last().unwrap().checked_add(3)
+        .ok_or_else(|| AocError::no_solution(DAY, "The device joltage exceeds 255 jolts"))?;
+    vec.push(device);
+    Ok(vec)
 }
 
 fn get_clusters(adapters: &[u8]) -> Vec<(usize, usize)> {
@@ -85,44 +92,73 @@ fn get_clusters(adapters: &[u8]) -> Vec<(usize, usize)> {
     clusters
 }
 
+pub struct Day10 {
+    input: String,
+}
+
+impl Solver for Day10 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day10 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_jolt_difference(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_number_of_possible_arrangements(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day10::{parse_input, get_jolt_difference, get_number_of_possible_arrangements, get_clusters};
+    use crate::error::AocError;
 
     //   use super::*;
     #[test]
     fn test_parse_input() {
         // vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4]
-        assert_eq!(vec![0, 1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22], parse_input(get_example1()));
+        assert_eq!(vec![0, 1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22], parse_input(get_example1()).unwrap());
     }
 
     #[test]
     fn test_jolt_difference() {
-        assert_eq!(35, get_jolt_difference(get_example1()));
-        assert_eq!(220, get_jolt_difference(get_example2()));
+        assert_eq!(35, get_jolt_difference(get_example1()).unwrap());
+        assert_eq!(220, get_jolt_difference(get_example2()).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day10.txt").unwrap();
-        println!("Jolt difference: {}", get_jolt_difference(input.as_str()));
+        println!("Jolt difference: {}", get_jolt_difference(input.as_str()).unwrap());
     }
 
     #[test]
     fn test_get_number_of_possible_arrangements() {
-        assert_eq!(8, get_number_of_possible_arrangements(get_example1()));
-        assert_eq!(19208, get_number_of_possible_arrangements(get_example2()));
+        assert_eq!(8, get_number_of_possible_arrangements(get_example1()).unwrap());
+        assert_eq!(19208, get_number_of_possible_arrangements(get_example2()).unwrap());
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day10.txt").unwrap();
-        println!("Possible arrangements: {}", get_number_of_possible_arrangements(input.as_str()));
+        println!("Possible arrangements: {}", get_number_of_possible_arrangements(input.as_str()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Err(AocError::parse(10, 2, 1, "Invalid value '256': number too large to fit in target type")), parse_input("16\n256\n"));
+        assert!(parse_input("16\n253\n").is_err());
     }
 
     #[test]
     fn test_get_clusters() {
-        let adapters = parse_input(get_example1());
+        let adapters = parse_input(get_example1()).unwrap();
         assert_eq!(vec![(2, 5), (6, 8)], get_clusters(&adapters));
     }
 
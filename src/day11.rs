@@ -1,3 +1,9 @@
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 11;
+
 #[derive(Debug, PartialEq, Clone, Eq)]
 pub enum Object {
     FLOOR,
@@ -23,83 +29,75 @@ pub enum Object {
     res
 }*/
 
-fn get_neighbours(grid: &[Object], index: usize, width: usize, max_distance: usize) -> Vec<usize> {
-    let max_distance = max_distance as isize;
-    let height = (grid.len() / width) as isize;
-    let width = width as isize;
-    let index = index as isize;
-    let mut res: Vec<usize> = Vec::with_capacity(8);
-    let pos_x = (index % width) as isize;
-    let pos_y = (index / width) as isize;
-    for y_offset in &[-1, 0, 1] {
-        for x_offset in &[-1, 0, 1] {
-            if *x_offset == 0 && *y_offset == 0 { continue; }
-            let mut distance_factor = 0;
-            while distance_factor < max_distance {
-                distance_factor += 1;
-                let test_x = (pos_x + distance_factor * x_offset) as isize;
-                let test_y = (pos_y + distance_factor * y_offset) as isize;
-
-                if test_x < 0 || test_y < 0 || test_x >= width as isize || test_y >= height as isize { break; }
-                if grid[(test_y * width + test_x) as usize] != Object::FLOOR {
-                    res.push((test_y * width + test_x) as usize);
-                    break;
-                }
-            }
-        }
-    }
-    res
+/// Returns the first seat in each direction that is at most `max_distance` cells away
+fn get_neighbours(grid: &Grid<Object>, seat: (usize, usize), max_distance: usize) -> Vec<(usize, usize)> {
+    grid.ray_cast(seat, max_distance, |object| *object != Object::FLOOR).collect()
 }
 
-fn get_number_of_occupied_neighbours(grid: &[Object], width: usize, seat: usize, max_viewdistance: usize) -> usize {
-    let neighbours = get_neighbours(grid, seat, width, max_viewdistance);
-    neighbours.iter().filter(|&x| grid[*x] == Object::OCCUPIED).count()
+fn get_number_of_occupied_neighbours(grid: &Grid<Object>, seat: (usize, usize), max_viewdistance: usize) -> usize {
+    let neighbours = get_neighbours(grid, seat, max_viewdistance);
+    neighbours.iter().filter(|&&x| grid[x] == Object::OCCUPIED).count()
 }
 
-fn parse_input(input: &str) -> Vec<Object> {
-    let mut res = Vec::new();
-    for row in input.trim().split('\n') {
-        for c in row.chars() {
-            res.push(match c {
-                '.' => Object::FLOOR,
-                'L' => Object::EMPTY,
-                '#' => Object::OCCUPIED,
-                _ => { panic!() }
-            });
-        }
-    }
-    res
+fn parse_input(input: &str) -> Result<Grid<Object>, AocError> {
+    Grid::parse(DAY, input, |c| match c {
+        '.' => Some(Object::FLOOR),
+        'L' => Some(Object::EMPTY),
+        '#' => Some(Object::OCCUPIED),
+        _ => None,
+    })
 }
 
-fn tick(grid: &mut [Object], width: usize, overpopulated_when: usize, max_viewdistance: usize) -> bool {
-    let grid_cloned = grid.to_owned();
-    for (k, v) in grid.iter_mut().enumerate() {
-        match v {
+fn tick(grid: &mut Grid<Object>, overpopulated_when: usize, max_viewdistance: usize) -> bool {
+    let grid_cloned = grid.clone();
+    for seat in grid_cloned.positions() {
+        match grid_cloned[seat] {
             Object::FLOOR => {}
             Object::EMPTY => {
-                if get_number_of_occupied_neighbours(&grid_cloned, width, k, max_viewdistance) == 0 {
-                    *v = Object::OCCUPIED;
+                if get_number_of_occupied_neighbours(&grid_cloned, seat, max_viewdistance) == 0 {
+                    grid[seat] = Object::OCCUPIED;
                 }
             }
             Object::OCCUPIED => {
-                if get_number_of_occupied_neighbours(&grid_cloned, width, k, max_viewdistance) >= overpopulated_when {
-                    *v = Object::EMPTY;
+                if get_number_of_occupied_neighbours(&grid_cloned, seat, max_viewdistance) >= overpopulated_when {
+                    grid[seat] = Object::EMPTY;
                 }
             }
         }
     }
-    grid != &grid_cloned[..]
+    *grid != grid_cloned
 }
 
-fn get_number_of_occupied_seats(grid: &[Object]) -> usize {
+fn get_number_of_occupied_seats(grid: &Grid<Object>) -> usize {
     grid.iter().filter(|&x| *x == Object::OCCUPIED).count()
 }
 
-pub fn get_number_of_occupied_seats_after_stabilisation(input: &str, overpopulated_when: usize, max_viewdistance: usize) -> usize {
-    let mut grid = parse_input(input);
-    let width = input.find('\n').unwrap();
-    while tick(&mut grid, width, overpopulated_when, max_viewdistance) {}
-    get_number_of_occupied_seats(&grid)
+pub fn get_number_of_occupied_seats_after_stabilisation(input: &str, overpopulated_when: usize, max_viewdistance: usize) -> Result<usize, AocError> {
+    let mut grid = parse_input(input)?;
+    while tick(&mut grid, overpopulated_when, max_viewdistance) {}
+    Ok(get_number_of_occupied_seats(&grid))
+}
+
+pub struct Day11 {
+    input: String,
+}
+
+impl Solver for Day11 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day11 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_number_of_occupied_seats_after_stabilisation(&self.input, 4, 1)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_number_of_occupied_seats_after_stabilisation(&self.input, 5, isize::MAX as usize)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
@@ -110,19 +108,19 @@ mod tests {
     #[test]
     fn test_one_tick() {
         let input = get_example1();
-        let mut grid = parse_input(input);
-        let expected = parse_input(get_example1_after1());
-        tick(&mut grid, input.find('\n').unwrap(), 4, 1);
+        let mut grid = parse_input(input).unwrap();
+        let expected = parse_input(get_example1_after1()).unwrap();
+        tick(&mut grid, 4, 1);
         assert_eq!(expected, grid);
     }
 
     #[test]
     fn test_two_ticks() {
         let input = get_example1();
-        let mut grid = parse_input(input);
-        tick(&mut grid, input.find('\n').unwrap(), 4, 1);
-        let expected = parse_input(get_example1_after2());
-        tick(&mut grid, input.find('\n').unwrap(), 4, 1);
+        let mut grid = parse_input(input).unwrap();
+        tick(&mut grid, 4, 1);
+        let expected = parse_input(get_example1_after2()).unwrap();
+        tick(&mut grid, 4, 1);
         assert_eq!(expected, grid);
     }
 
@@ -130,22 +128,21 @@ mod tests {
     #[test]
     fn test_example1_stabilise() {
         let input = get_example1();
-        let mut grid = parse_input(input);
-        let width = input.find('\n').unwrap();
+        let mut grid = parse_input(input).unwrap();
         let mut count = 0;
-        while tick(&mut grid, width, 4, 1) { count += 1; }
+        while tick(&mut grid, 4, 1) { count += 1; }
         assert_eq!(5, count);
     }
 
     #[test]
     fn test_example1() {
-        assert_eq!(37, get_number_of_occupied_seats_after_stabilisation(get_example1(), 4, 1));
+        assert_eq!(37, get_number_of_occupied_seats_after_stabilisation(get_example1(), 4, 1).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day11.txt").unwrap();
-        println!("{}", get_number_of_occupied_seats_after_stabilisation(input.as_str(), 4, 1));
+        println!("{}", get_number_of_occupied_seats_after_stabilisation(input.as_str(), 4, 1).unwrap());
     }
 
     #[test]
@@ -153,8 +150,8 @@ mod tests {
         let input = r".............
 .L.L.#.#.#.#.
 .............";
-        let grid = parse_input(input);
-        assert_eq!(vec![16], get_neighbours(&grid, 14, 13, isize::max_value() as usize));
+        let grid = parse_input(input).unwrap();
+        assert_eq!(vec![(3, 1)], get_neighbours(&grid, (1, 1), isize::max_value() as usize));
         let input = r".##.##.
 #.#.#.#
 ##...##
@@ -162,14 +159,20 @@ mod tests {
 ##...##
 #.#.#.#
 .##.##.";
-        let grid = parse_input(input);
-        assert_eq!(Vec::new() as Vec<usize>, get_neighbours(&grid, 24, 7, isize::max_value() as usize));
+        let grid = parse_input(input).unwrap();
+        assert_eq!(Vec::new() as Vec<(usize, usize)>, get_neighbours(&grid, (3, 3), isize::max_value() as usize));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Err(AocError::parse(11, 2, 3, "Unknown character 'x'")), parse_input("L.L\n.Lx\n"));
+        assert_eq!(Err(AocError::parse(11, 2, 1, "Expected 3 columns, found 2")), parse_input("L.L\n.L\n"));
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day11.txt").unwrap();
-        println!("{}", get_number_of_occupied_seats_after_stabilisation(input.as_str(), 5, isize::max_value() as usize));
+        println!("{}", get_number_of_occupied_seats_after_stabilisation(input.as_str(), 5, isize::max_value() as usize).unwrap());
     }
 
     fn get_example1() -> &'static str {
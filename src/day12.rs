@@ -1,3 +1,9 @@
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 12;
+
 pub enum Direction {
     NORTH = 0,
     EAST = 90,
@@ -24,12 +30,27 @@ impl Direction {
     }
 }
 
-fn navigate(input: &str) -> (i64, i64) {
+/// Splits the instructions into action and value, checking that turns are multiples of 90 degrees
+fn parse_instructions(input: &str) -> Result<Vec<(&str, i64)>, AocError> {
+    lines(input)
+        .map(|instruction| {
+            if !instruction.starts_with(|c| "NSEWLRF".contains(c)) {
+                return Err(AocError::parse_at(DAY, input, instruction, format!("Expected one of N, S, E, W, L, R or F, found '{}'", instruction)));
+            }
+            let (action, distance) = instruction.split_at(1);
+            let distance: i64 = parse_fragment(DAY, input, distance)?;
+            if (action == "L" || action == "R") && distance % 90 != 0 {
+                return Err(AocError::parse_at(DAY, input, instruction, format!("Cannot turn by {} degrees", distance)));
+            }
+            Ok((action, distance))
+        })
+        .collect()
+}
+
+fn navigate(input: &str) -> Result<(i64, i64), AocError> {
     let mut position = (0, 0);
     let mut direction = Direction::EAST;
-    for instruction in input.trim().split('\n') {
-        let (action, distance) = instruction.split_at(1);
-        let distance: i64 = distance.parse().unwrap();
+    for (action, distance) in parse_instructions(input)? {
         match action {
             "N" => position.1 += distance,
             "S" => position.1 -= distance,
@@ -47,16 +68,14 @@ fn navigate(input: &str) -> (i64, i64) {
         }
     }
 
-    position
+    Ok(position)
 }
 
 
-fn navigate_new_instructions(input: &str) -> (i64, i64) {
+fn navigate_new_instructions(input: &str) -> Result<(i64, i64), AocError> {
     let mut position = (0, 0);
     let mut waypoint: (i64, i64) = (10, 1);
-    for instruction in input.trim().split('\n') {
-        let (action, distance) = instruction.split_at(1);
-        let distance: i64 = distance.parse().unwrap();
+    for (action, distance) in parse_instructions(input)? {
         match action {
             "N" => waypoint.1 += distance,
             "S" => waypoint.1 -= distance,
@@ -69,7 +88,7 @@ fn navigate_new_instructions(input: &str) -> (i64, i64) {
         }
     }
 
-    position
+    Ok(position)
 }
 
 fn rotate_waypoint(waypoint: (i64, i64), angle: i64) -> (i64, i64) {
@@ -85,16 +104,39 @@ fn get_manhattan_distance(point: (i64, i64)) -> usize {
     (point.0.abs() + point.1.abs()) as usize
 }
 
-pub fn get_travel_distance(input: &str, using_waypoint: bool) -> usize {
+pub fn get_travel_distance(input: &str, using_waypoint: bool) -> Result<usize, AocError> {
     match using_waypoint {
-        true => get_manhattan_distance(navigate_new_instructions(input)),
-        false => get_manhattan_distance(navigate(input))
+        true => Ok(get_manhattan_distance(navigate_new_instructions(input)?)),
+        false => Ok(get_manhattan_distance(navigate(input)?))
+    }
+}
+
+pub struct Day12 {
+    input: String,
+}
+
+impl Solver for Day12 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day12 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_travel_distance(&self.input, false)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_travel_distance(&self.input, true)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day12::{navigate, get_manhattan_distance, get_travel_distance};
+    use crate::error::AocError;
 
     #[test]
     fn test_navigate() {
@@ -103,7 +145,7 @@ N3
 F7
 R90
 F11";
-        assert_eq!((17, -8), navigate(input));
+        assert_eq!((17, -8), navigate(input).unwrap());
     }
 
     #[test]
@@ -118,13 +160,13 @@ N3
 F7
 R90
 F11";
-        assert_eq!(25, get_travel_distance(input, false));
+        assert_eq!(25, get_travel_distance(input, false).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day12.txt").unwrap();
-        println!("{}", get_travel_distance(input.as_str(), false));
+        println!("{}", get_travel_distance(input.as_str(), false).unwrap());
     }
 
     #[test]
@@ -134,12 +176,19 @@ N3
 F7
 R90
 F11";
-        assert_eq!(286, get_travel_distance(input, true));
+        assert_eq!(286, get_travel_distance(input, true).unwrap());
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day12.txt").unwrap();
-        println!("{}", get_travel_distance(input.as_str(), true));
+        println!("{}", get_travel_distance(input.as_str(), true).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_instructions() {
+        assert_eq!(Err(AocError::parse(12, 2, 1, "Cannot turn by 45 degrees")), get_travel_distance("F10\nR45", false));
+        assert_eq!(Err(AocError::parse(12, 1, 1, "Expected one of N, S, E, W, L, R or F, found 'X10'")), get_travel_distance("X10", true));
+        assert_eq!(Err(AocError::parse(12, 1, 2, "Invalid value '': cannot parse integer from empty string")), get_travel_distance("F", true));
+    }
+}
@@ -1,11 +1,41 @@
-pub fn get_earliest_bus(input: &str) -> (u64, u64) {
-    let (arrival, busses) = input.trim().split_once('\n').unwrap();
-    let arrival: u64 = arrival.parse().unwrap();
-    let busses: Vec<u64> = busses
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 13;
+
+/// Splits the notes into the arrival time and the bus list
+fn split_notes(input: &str) -> Result<(&str, &str), AocError> {
+    let mut notes = lines(input);
+    notes.next().zip(notes.next())
+        .ok_or_else(|| AocError::parse_at(DAY, input, input.trim_start(), "Expected the arrival time and the bus list on two lines"))
+}
+
+/// Parses the comma-separated bus IDs, where `x` is a bus that is out of service.
+/// `busses` has to be a slice of `input`
+fn parse_busses(input: &str, busses: &str) -> Result<Vec<Option<u64>>, AocError> {
+    busses
         .split(',')
-        .filter(|&x| x != "x")
-        .map(|x| x.parse().unwrap())
+        .map(|x| match x {
+            "x" => Ok(None),
+            _ => match parse_fragment(DAY, input, x)? {
+                0 => Err(AocError::parse_at(DAY, input, x, "Bus IDs must not be 0")),
+                bus => Ok(Some(bus)),
+            }
+        })
+        .collect()
+}
+
+pub fn get_earliest_bus(input: &str) -> Result<(u64, u64), AocError> {
+    let (arrival, busses) = split_notes(input)?;
+    let arrival: u64 = parse_fragment(DAY, input, arrival)?;
+    let busses: Vec<u64> = parse_busses(input, busses)?
+        .into_iter()
+        .flatten()
         .collect();
+    if busses.is_empty() {
+        return Err(AocError::no_solution(DAY, "All busses are out of service"));
+    }
 
     let mut best_wait = u64::max_value();
     let mut best_bus = 0;
@@ -17,12 +47,12 @@ pub fn get_earliest_bus(input: &str) -> (u64, u64) {
             best_bus = bus;
         }
     }
-    (best_bus, best_wait)
+    Ok((best_bus, best_wait))
 }
 
-pub fn get_timestamp_sequence(input: &str) -> i64 {
-    let (_, busses) = input.trim().split_once('\n').unwrap();
-    chinese_remainder(busses)
+pub fn get_timestamp_sequence(input: &str) -> Result<i64, AocError> {
+    let (_, busses) = split_notes(input)?;
+    chinese_remainder(busses).map_err(|e| e.relative_to(input, busses))
 }
 /*
 fn get_timestamp_sequence_busses(busses: &str) -> u64 {
@@ -69,10 +99,10 @@ fn eea(a: i64, b: i64) -> (i64, i64, i64) {
     (d, s, t)
 }
 
-pub fn chinese_remainder(busses: &str) -> i64 {
-    let busses: Vec<(i64, i64)> = busses
-        .split(',')
-        .map(|x| if x == "x" { 1 } else { x.parse().unwrap() })
+pub fn chinese_remainder(busses: &str) -> Result<i64, AocError> {
+    let busses: Vec<(i64, i64)> = parse_busses(busses, busses)?
+        .into_iter()
+        .map(|bus| bus.unwrap_or(1))
         .enumerate()
         .filter(|(_, bus)| *bus != 1)
         .map(|(idx, bus)| (idx as i64, bus as i64))
@@ -102,19 +132,43 @@ pub fn chinese_remainder(busses: &str) -> i64 {
         .enumerate()
         .map(|(idx, (_, offset))| e[idx] * offset)
         .sum::<i64>() % m_all;
-    (remainder + m_all) % m_all
+    Ok((remainder + m_all) % m_all)
+}
+
+pub struct Day13 {
+    input: String,
+}
+
+impl Solver for Day13 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day13 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        let (bus, wait) = get_earliest_bus(&self.input)?;
+        Ok((bus * wait).into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_timestamp_sequence(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day13::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_get_earliest_bus() {
         let input = r"939
 7,13,x,x,59,x,31,19
 ";
-        assert_eq!((59, 5), get_earliest_bus(input));
+        assert_eq!((59, 5), get_earliest_bus(input).unwrap());
     }
 
     #[test]
@@ -122,14 +176,14 @@ mod tests {
         let input = r"939
 7,13,x,x,59,x,31,19
 ";
-        let (bus, wait) = get_earliest_bus(input);
+        let (bus, wait) = get_earliest_bus(input).unwrap();
         assert_eq!(295, bus * wait);
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day13.txt").unwrap();
-        let (bus, wait) = get_earliest_bus(input.as_str());
+        let (bus, wait) = get_earliest_bus(input.as_str()).unwrap();
         println!("best bus: {}, wait: {}\n => {}", bus, wait, bus * wait);
     }
 
@@ -138,21 +192,28 @@ mod tests {
         let input = r"939
 7,13,x,x,59,x,31,19
 ";
-        assert_eq!(1068781, get_timestamp_sequence(input));
+        assert_eq!(1068781, get_timestamp_sequence(input).unwrap());
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day13.txt").unwrap();
-        println!("{}", get_timestamp_sequence(input.as_str()));
+        println!("{}", get_timestamp_sequence(input.as_str()).unwrap());
     }
 
     #[test]
     fn test_chinese_remainder() {
-        assert_eq!(3417, chinese_remainder("17,x,13,19"));
-        assert_eq!(754018, chinese_remainder("67,7,59,61"));
-        assert_eq!(779210, chinese_remainder("67,x,7,59,61"));
-        assert_eq!(1261476, chinese_remainder("67,7,x,59,61"));
-        assert_eq!(1202161486, chinese_remainder("1789,37,47,1889"));
+        assert_eq!(3417, chinese_remainder("17,x,13,19").unwrap());
+        assert_eq!(754018, chinese_remainder("67,7,59,61").unwrap());
+        assert_eq!(779210, chinese_remainder("67,x,7,59,61").unwrap());
+        assert_eq!(1261476, chinese_remainder("67,7,x,59,61").unwrap());
+        assert_eq!(1202161486, chinese_remainder("1789,37,47,1889").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_notes() {
+        assert_eq!(Err(AocError::parse(13, 2, 6, "Bus IDs must not be 0")), get_timestamp_sequence("939\n7,13,0,x"));
+        assert_eq!(Err(AocError::parse(13, 2, 3, "Invalid value 'y': invalid digit found in string")), get_earliest_bus("939\n7,y"));
+        assert_eq!(Err(AocError::parse(13, 1, 1, "Expected the arrival time and the bus list on two lines")), get_earliest_bus("939"));
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,10 @@
 use std::collections::{HashMap, HashSet};
 use regex::Regex;
-use std::error::Error;
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 14;
 
 #[derive(Debug, PartialEq)]
 struct Mask {
@@ -10,12 +14,18 @@ struct Mask {
 }
 
 impl Mask {
-    fn new(mask: &str) -> Mask {
-        Mask {
+    fn new(mask: &str) -> Result<Mask, AocError> {
+        if mask.len() > 36 {
+            return Err(AocError::parse(DAY, 1, 37, format!("Masks have 36 bits, found {}", mask.len())));
+        }
+        if let Some((idx, c)) = mask.chars().enumerate().find(|(_, c)| !"01X".contains(*c)) {
+            return Err(AocError::parse(DAY, 1, idx + 1, format!("Unexpected character '{}' in mask", c)));
+        }
+        Ok(Mask {
             and_mask: u64::from_str_radix(mask.replace("X", "1").as_str(), 2).unwrap(),
             or_mask: u64::from_str_radix(mask.replace("X", "0").as_str(), 2).unwrap(),
             raw: mask.to_owned(),
-        }
+        })
     }
 
     fn apply(&self, value: u64) -> u64 {
@@ -52,7 +62,7 @@ impl Decoder {
         }
     }
 
-    pub fn run_programme(&mut self, input: &str) -> Result<(), Box<dyn Error>> {
+    pub fn run_programme(&mut self, input: &str) -> Result<(), AocError> {
         match self.version {
             DecoderVersion::V1 => { self._run_programme_v1(input) }
             DecoderVersion::V2 => { self._run_programme_v2(input) }
@@ -63,16 +73,15 @@ impl Decoder {
         self.memory.iter().map(|(_, v)| *v).sum()
     }
 
-    fn _run_programme_v1(&mut self, input: &str) -> Result<(), Box<dyn Error>> {
-        for line in input.trim().split('\n') {
+    fn _run_programme_v1(&mut self, input: &str) -> Result<(), AocError> {
+        for line in lines(input) {
             if line.starts_with("mask = ") {
-                self.mask = Some(Mask::new(line.strip_prefix("mask = ").ok_or("Error stripping prefix")?));
+                let mask = line.strip_prefix("mask = ").unwrap();
+                self.mask = Some(Mask::new(mask).map_err(|e| e.relative_to(input, mask))?);
             } else {
-                let res = &RE.captures(line).ok_or("Error applying regex")?;
-                let index: u64 = res.get(1).ok_or("Error getting memory index")?.as_str().parse()?;
-                let value: u64 = res.get(2).ok_or("Error getting memory index")?.as_str().parse()?;
+                let (index, value) = parse_assignment(input, line)?;
                 match &self.mask {
-                    None => { panic!("Mask is not defined"); }
+                    None => { return Err(AocError::parse_at(DAY, input, line, "Mask is not defined")); }
                     Some(m) => { self.memory.insert(index, m.apply(value)); }
                 }
             }
@@ -80,16 +89,15 @@ impl Decoder {
         Ok(())
     }
 
-    fn _run_programme_v2(&mut self, input: &str) -> Result<(), Box<dyn Error>> {
-        for line in input.trim().split('\n') {
+    fn _run_programme_v2(&mut self, input: &str) -> Result<(), AocError> {
+        for line in lines(input) {
             if line.starts_with("mask = ") {
-                self.mask = Some(Mask::new(line.strip_prefix("mask = ").ok_or("Error stripping prefix")?));
+                let mask = line.strip_prefix("mask = ").unwrap();
+                self.mask = Some(Mask::new(mask).map_err(|e| e.relative_to(input, mask))?);
             } else {
-                let res = &RE.captures(line).ok_or("Error applying regex")?;
-                let index: u64 = res.get(1).ok_or("Error getting memory index")?.as_str().parse()?;
-                let value: u64 = res.get(2).ok_or("Error getting memory index")?.as_str().parse()?;
+                let (index, value) = parse_assignment(input, line)?;
                 match &self.mask {
-                    None => { panic!("Mask is not defined"); }
+                    None => { return Err(AocError::parse_at(DAY, input, line, "Mask is not defined")); }
                     Some(m) => {
                         for address in m.apply_on_address(index) {
                             self.memory.insert(address, value);
@@ -110,13 +118,51 @@ pub enum DecoderVersion {
 
 
 lazy_static! {
-    static ref RE: Regex = Regex::new(r"mem\[(\d+)\] = (\d+)").unwrap();
+    static ref RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+}
+
+/// Parses a line of the form `mem[<address>] = <value>`. `line` has to be a slice of `input`
+fn parse_assignment(input: &str, line: &str) -> Result<(u64, u64), AocError> {
+    let res = RE.captures(line).ok_or_else(|| AocError::parse_at(DAY, input, line,
+        format!("Expected 'mask = <mask>' or 'mem[<address>] = <value>', found '{}'", line)))?;
+    Ok((parse_fragment(DAY, input, res.get(1).unwrap().as_str())?,
+        parse_fragment(DAY, input, res.get(2).unwrap().as_str())?))
+}
+
+pub struct Day14 {
+    input: String,
+}
+
+impl Solver for Day14 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day14 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(self.run(DecoderVersion::V1)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(self.run(DecoderVersion::V2)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
+impl Day14 {
+    fn run(&self, version: DecoderVersion) -> Result<u64, AocError> {
+        let mut decoder = Decoder::new(version);
+        decoder.run_programme(&self.input)?;
+        Ok(decoder.get_sum_of_memory())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use crate::day14::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_binary_number_to_string() {
@@ -126,14 +172,14 @@ mod tests {
 
     #[test]
     fn test_get_mask() {
-        assert_eq!(Mask { and_mask: 253, or_mask: 64, raw: String::from("X1XXXX0X") }, Mask::new("X1XXXX0X"));
+        assert_eq!(Mask { and_mask: 253, or_mask: 64, raw: String::from("X1XXXX0X") }, Mask::new("X1XXXX0X").unwrap());
     }
 
     #[test]
     fn test_apply_mask() {
-        assert_eq!(73, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").apply(11));
-        assert_eq!(101, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").apply(101));
-        assert_eq!(64, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").apply(0));
+        assert_eq!(73, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap().apply(11));
+        assert_eq!(101, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap().apply(101));
+        assert_eq!(64, Mask::new("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap().apply(0));
     }
 
     #[test]
@@ -161,7 +207,7 @@ mem[8] = 0";
 
     #[test]
     fn test_apply_bitmask_on_address() {
-        let mask = Mask::new("000000000000000000000000000000X1001X");
+        let mask = Mask::new("000000000000000000000000000000X1001X").unwrap();
         let mut expected: HashSet<u64> = HashSet::new();
         expected.extend(&[26, 27, 58, 59]);
         assert_eq!(expected, mask.apply_on_address(42));
@@ -185,4 +231,13 @@ mem[26] = 1";
         decoder.run_programme(input.as_str()).unwrap();
         println!("{}", decoder.get_sum_of_memory());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_programme() {
+        let mut decoder = Decoder::new(DecoderVersion::V1);
+        assert_eq!(Err(AocError::parse(14, 1, 1, "Mask is not defined")), decoder.run_programme("mem[8] = 11"));
+        assert_eq!(Err(AocError::parse(14, 2, 10, "Unexpected character 'Y' in mask")), decoder.run_programme("mask = XX\nmask = XXY"));
+        assert_eq!(Err(AocError::parse(14, 2, 1, "Expected 'mask = <mask>' or 'mem[<address>] = <value>', found 'mem[8] 11'")),
+                   decoder.run_programme("mask = XX\nmem[8] 11"));
+    }
+}
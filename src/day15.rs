@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use crate::error::{parse_fragment, AocError};
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 15;
 
 pub struct Sequence {
     starting_numbers: Vec<usize>,
@@ -40,6 +44,37 @@ impl Iterator for Sequence {
     }
 }
 
+pub struct Day15 {
+    starting_numbers: Vec<usize>,
+}
+
+impl Day15 {
+    fn get_nth_number(&self, n: usize) -> Answer {
+        Sequence::new(&self.starting_numbers).nth(n - 1).unwrap().into()
+    }
+}
+
+impl Solver for Day15 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let starting_numbers = input.trim().split(',')
+            .map(|number| parse_fragment(DAY, input, number.trim()))
+            .collect::<Result<Vec<usize>, _>>()?;
+        Ok(Day15 { starting_numbers })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(self.get_nth_number(2020))
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(self.get_nth_number(30_000_000))
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day15::Sequence;
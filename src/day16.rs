@@ -1,5 +1,9 @@
-use std::error::Error;
 use std::collections::{HashMap, HashSet};
+use crate::error::{parse_fragment, AocError};
+use crate::input::{blocks, lines};
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 16;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Rule {
@@ -9,18 +13,20 @@ pub struct Rule {
 }
 
 impl Rule {
-    pub fn new(rule: &str) -> Option<Rule> {
-        fn convert_to_u32_range(range: &str) -> Option<(u32, u32)> {
-            let (v1, v2) = range.split_once('-')?;
-            Some((v1.parse().ok()?, v2.parse().ok()?))
+    pub fn new(rule: &str) -> Result<Rule, AocError> {
+        fn convert_to_u32_range(rule: &str, range: &str) -> Result<(u32, u32), AocError> {
+            let (v1, v2) = range.split_once('-')
+                .ok_or_else(|| AocError::parse_at(DAY, rule, range, format!("Expected '<min>-<max>', found '{}'", range)))?;
+            Ok((parse_fragment(DAY, rule, v1)?, parse_fragment(DAY, rule, v2)?))
         }
 
-        let (name, rest) = rule.trim().split_once(": ")?;
-        let (range1, range2) = rest.trim().split_once(" or ")?;
-        Some(Rule {
+        let expected = || AocError::parse_at(DAY, rule, rule.trim(), "Expected '<field>: <min>-<max> or <min>-<max>'");
+        let (name, rest) = rule.trim().split_once(": ").ok_or_else(expected)?;
+        let (range1, range2) = rest.trim().split_once(" or ").ok_or_else(expected)?;
+        Ok(Rule {
             name: name.to_string(),
-            range1: convert_to_u32_range(range1)?,
-            range2: convert_to_u32_range(range2)?,
+            range1: convert_to_u32_range(rule, range1)?,
+            range2: convert_to_u32_range(rule, range2)?,
         })
     }
 
@@ -30,10 +36,9 @@ impl Rule {
     }
 }
 
-fn get_sum_of_invalid_values(rules: &[Rule], ticket: &str) -> u32 {
-    ticket.split(',')
-        .map(|v| v.parse::<u32>().unwrap())
-        .filter(|v| rules.iter().all(|r| !r.is_valid(*v)))
+fn get_sum_of_invalid_values(rules: &[Rule], ticket: &[u32]) -> u32 {
+    ticket.iter()
+        .filter(|v| rules.iter().all(|r| !r.is_valid(**v)))
         .sum()
 }
 
@@ -49,48 +54,53 @@ fn is_valid(ticket: &str, rules: &[Rule]) -> bool {
     true
 }
 
-pub fn get_ticket_scanning_error_rate(input: &str) -> Result<u32, Box<dyn Error>> {
-    let rules = get_rules(input).ok_or("Error getting rules")?;
-    let nearby_tickets = get_nearby_tickets(input).ok_or("Error getting nearby tickets")?;
+/// Parses the comma-separated values of a ticket. `ticket` has to be a slice of `input`
+fn parse_ticket(input: &str, ticket: &str) -> Result<Vec<u32>, AocError> {
+    ticket.split(',')
+        .map(|v| parse_fragment(DAY, input, v.trim()))
+        .collect()
+}
+
+pub fn get_ticket_scanning_error_rate(input: &str) -> Result<u32, AocError> {
+    let rules = get_rules(input)?;
+    let nearby_tickets = get_nearby_tickets(input)?;
     let mut sum = 0;
     for ticket in nearby_tickets {
-        sum += get_sum_of_invalid_values(&rules, &ticket);
+        sum += get_sum_of_invalid_values(&rules, &parse_ticket(input, ticket)?);
     }
     Ok(sum)
 }
 
-pub fn get_field_order(rules: &[Rule], tickets: &[&str]) -> Result<HashMap<String, Vec<usize>>, Box<dyn Error>> {
+pub fn get_field_order(rules: &[Rule], tickets: &[&str]) -> Result<HashMap<String, Vec<usize>>, AocError> {
     let mut valid_tickets: Vec<&str> = Vec::new();
     for &ticket in tickets {
         if is_valid(ticket, rules) {
             valid_tickets.push(ticket);
         }
     }
+    let num_fields = valid_tickets.first()
+        .ok_or_else(|| AocError::no_solution(DAY, "There are no valid tickets"))?
+        .split(',').count();
+    if let Some(ticket) = valid_tickets.iter().find(|ticket| ticket.split(',').count() != num_fields) {
+        return Err(AocError::no_solution(DAY, format!("Ticket {} does not have {} fields", ticket, num_fields)));
+    }
     let mut possibilities: HashMap<String, Vec<usize>> = HashMap::new();
-    for i in 0..valid_tickets.get(0).ok_or("No valid tickets")?.split(',').count() {
+    for i in 0..num_fields {
         'outer: for rule in rules {
             for &ticket in &valid_tickets {
-                let val: u32 = ticket.split(',').nth(i).ok_or("No nth segment in ticket")?.parse()?;
+                let val: u32 = ticket.split(',').nth(i).unwrap().parse().unwrap();
                 if !rule.is_valid(val) {
                     // println!("Field {} is not rule {:?} because of ticket {}", i, rule, ticket);
                     continue 'outer;
                 }
             }
-            match possibilities.get_mut(&rule.name) {
-                None => {
-                    possibilities.insert(rule.name.clone(), Vec::new());
-                    possibilities.get_mut(&rule.name).ok_or("no rule found")?.push(i);
-                }
-                Some(p) => {
-                    p.push(i);
-                }
-            }
+            possibilities.entry(rule.name.clone()).or_default().push(i);
         }
     }
     Ok(possibilities)
 }
 
-fn remove_used_options(mappings: &mut HashMap<String, Vec<usize>>) {
+fn remove_used_options(mappings: &mut HashMap<String, Vec<usize>>) -> Result<(), AocError> {
     while mappings.iter().any(|(_field_name, field_positions)| field_positions.len() > 1) {
         let mut singles: HashSet<usize> = HashSet::new();
         for (_field_name, field_positions) in mappings.iter() {
@@ -98,64 +108,96 @@ fn remove_used_options(mappings: &mut HashMap<String, Vec<usize>>) {
                 singles.insert(*field_positions.get(0).unwrap());
             }
         }
+        let mut changed = false;
         for single in singles {
             for (_field_name, field_positions) in mappings.iter_mut() {
-                if field_positions.len() > 1 {
+                if field_positions.len() > 1 && field_positions.contains(&single) {
                     field_positions.retain(|x| *x != single);
+                    changed = true;
                 }
             }
         }
+        if !changed {
+            return Err(AocError::no_solution(DAY, "The field order is ambiguous"));
+        }
     }
+    Ok(())
 }
 
-fn get_rules(input: &str) -> Option<Vec<Rule>> {
+/// Returns the section with the given index, where sections are separated by blank lines
+fn get_section<'a>(input: &'a str, index: usize, name: &str) -> Result<&'a str, AocError> {
+    blocks(input).nth(index)
+        .ok_or_else(|| AocError::parse_at(DAY, input, &input[input.len()..], format!("Missing section '{}'", name)))
+}
+
+fn get_rules(input: &str) -> Result<Vec<Rule>, AocError> {
     let mut rules = Vec::new();
-    let mut sections = input.split("\n\n");
-    let rules_str = sections.next()?;
-    for r in rules_str.trim().split('\n') {
-        rules.push(Rule::new(r)?);
+    let rules_str = get_section(input, 0, "rules")?;
+    for r in lines(rules_str) {
+        rules.push(Rule::new(r).map_err(|e| e.relative_to(input, r))?);
     }
-    Some(rules)
+    Ok(rules)
 }
 
-fn get_nearby_tickets(input: &str) -> Option<Vec<String>> {
-    Some(input
-        .split("\n\n")
-        .nth(2)?
-        .trim()
-        .split('\n')
-        .skip(1)
-        .map(|s| s.to_owned())
-        .collect())
+fn get_nearby_tickets(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(lines(get_section(input, 2, "nearby tickets")?).skip(1).collect())
 }
 
-fn get_my_ticket(input: &str) -> Option<Vec<usize>> {
-    let my_ticket = input
-        .split("\n\n")
-        .nth(1)?
-        .trim()
-        .split_once('\n')?.1;
+fn get_my_ticket(input: &str) -> Result<Vec<u32>, AocError> {
+    let section = get_section(input, 1, "your ticket")?;
+    let my_ticket = lines(section).nth(1)
+        .ok_or_else(|| AocError::parse_at(DAY, input, section, "Expected 'your ticket:' followed by the ticket"))?;
 
-    Some(my_ticket.split(',').map(|v| v.parse().unwrap()).collect())
+    parse_ticket(input, my_ticket)
 }
 
-pub fn get_checksum(input: &str) -> Option<usize> {
+pub fn get_checksum(input: &str) -> Result<usize, AocError> {
     let nearby_tickets = get_nearby_tickets(&input)?;
-    let nearby_tickets: Vec<&str> = nearby_tickets.iter().map(|s| s.as_str()).collect();
+    for ticket in &nearby_tickets {
+        parse_ticket(input, ticket)?;
+    }
     let rules = get_rules(&input)?;
-    let mut field_order = get_field_order(&rules, &nearby_tickets).ok()?;
-    remove_used_options(&mut field_order);
+    let mut field_order = get_field_order(&rules, &nearby_tickets)?;
+    if let Some(rule) = rules.iter().find(|rule| !field_order.contains_key(&rule.name)) {
+        return Err(AocError::no_solution(DAY, format!("No field matches the rule for {}", rule.name)));
+    }
+    remove_used_options(&mut field_order)?;
     let my_ticket = get_my_ticket(&input)?;
-    Some(field_order.iter()
+    field_order.iter()
         .filter(|(field_name, _)| field_name.starts_with("departure"))
-        .map(|(_, field_positions)| my_ticket[field_positions[0]])
-        .product())
+        .map(|(field_name, field_positions)| field_positions.get(0)
+            .and_then(|&position| my_ticket.get(position))
+            .map(|&value| value as usize)
+            .ok_or_else(|| AocError::no_solution(DAY, format!("Cannot determine the value of {} on your ticket", field_name))))
+        .product()
 }
 
+pub struct Day16 {
+    input: String,
+}
+
+impl Solver for Day16 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day16 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_ticket_scanning_error_rate(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_checksum(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use crate::day16::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_new_rule() {
@@ -208,10 +250,10 @@ mod tests {
                          Rule::new("row: 6-11 or 33-44").unwrap(),
                          Rule::new("seat: 13-40 or 45-50").unwrap()
         ];
-        assert_eq!(0, get_sum_of_invalid_values(&rules, "7,3,47"));
-        assert_eq!(4, get_sum_of_invalid_values(&rules, "40,4,50"));
-        assert_eq!(55, get_sum_of_invalid_values(&rules, "55,2,20"));
-        assert_eq!(12, get_sum_of_invalid_values(&rules, "38,6,12"));
+        assert_eq!(0, get_sum_of_invalid_values(&rules, &[7, 3, 47]));
+        assert_eq!(4, get_sum_of_invalid_values(&rules, &[40, 4, 50]));
+        assert_eq!(55, get_sum_of_invalid_values(&rules, &[55, 2, 20]));
+        assert_eq!(12, get_sum_of_invalid_values(&rules, &[38, 6, 12]));
     }
 
     #[test]
@@ -288,7 +330,6 @@ nearby tickets:
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day16.txt").unwrap();
         let nearby_tickets = get_nearby_tickets(&input).unwrap();
-        let nearby_tickets: Vec<&str> = nearby_tickets.iter().map(|s| s.as_str()).collect();
         let rules = get_rules(&input).unwrap();
         println!("{:?}", get_field_order(&rules, &nearby_tickets));
 
@@ -326,4 +367,14 @@ nearby tickets:
         let input = std::fs::read_to_string("resources/day16.txt").unwrap();
         assert_eq!(453459307723, get_checksum(&input).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_notes() {
+        assert_eq!(Err(AocError::parse(16, 2, 14, "Expected '<min>-<max>', found '6'")),
+                   get_ticket_scanning_error_rate("class: 1-3 or 5-7\nrow: 6-11 or 6\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,3,47"));
+        assert_eq!(Err(AocError::parse(16, 8, 3, "Invalid value 'x': invalid digit found in string")),
+                   get_ticket_scanning_error_rate("class: 1-3 or 5-7\nrow: 6-11 or 33-44\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,x,47"));
+        assert_eq!(Err(AocError::parse(16, 4, 7, "Missing section 'nearby tickets'")),
+                   get_ticket_scanning_error_rate("class: 1-3 or 5-7\n\nyour ticket:\n7,1,14"));
+    }
+}
@@ -1,5 +1,18 @@
 use std::collections::HashSet;
 use std::ops::Range;
+use crate::error::AocError;
+use crate::grid;
+
+const DAY: u8 = 17;
+
+/// Parses the initial state, where '#' marks an active cube
+fn parse_cells(initial_state: &str) -> Result<grid::Grid<bool>, AocError> {
+    grid::Grid::parse(DAY, initial_state, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Grid {
@@ -10,20 +23,17 @@ pub struct Grid {
 
 #[allow(dead_code)]
 impl Grid {
-    pub fn new(initial_state: &str, survive: Range<usize>, revive: Range<usize>) -> Grid {
-        let mut grid = HashSet::new();
-        for (y, row) in initial_state.trim().split('\n').enumerate() {
-            for (x, chr) in row.chars().enumerate() {
-                if chr == '#' {
-                    grid.insert((x as isize, y as isize, 0_isize));
-                }
-            }
-        }
-        Grid {
-            active_cells: grid,
+    pub fn new(initial_state: &str, survive: Range<usize>, revive: Range<usize>) -> Result<Grid, AocError> {
+        let cells = parse_cells(initial_state)?;
+        let active_cells = cells.positions()
+            .filter(|&position| cells[position])
+            .map(|(x, y)| (x as isize, y as isize, 0_isize))
+            .collect();
+        Ok(Grid {
+            active_cells,
             survive,
             revive,
-        }
+        })
     }
 
     pub fn tick_n_times(&mut self, count: usize) {
@@ -103,7 +113,7 @@ mod tests {
         expected.insert((2, 2, 0));
         let grid = Grid::new(r".#.
 ..#
-###", 2..4, 3..4);
+###", 2..4, 3..4).unwrap();
         assert_eq!(expected, grid.active_cells);
     }
 
@@ -112,7 +122,7 @@ mod tests {
         let input = r".#.
 ..#
 ###";
-        let mut grid = Grid::new(input, 2..4, 3..4);
+        let mut grid = Grid::new(input, 2..4, 3..4).unwrap();
         grid.tick_n_times(6);
         assert_eq!(112, grid.active_cells.len());
     }
@@ -120,7 +130,7 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day17.txt").unwrap();
-        let mut grid = Grid::new(&input, 2..4, 3..4);
+        let mut grid = Grid::new(&input, 2..4, 3..4).unwrap();
         grid.tick_n_times(6);
         println!("active cells: {}", grid.count_active_cells());
     }
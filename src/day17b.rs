@@ -1,5 +1,19 @@
 use std::collections::HashSet;
 use std::ops::Range;
+use crate::error::AocError;
+use crate::grid;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 17;
+
+/// Parses the initial state, where '#' marks an active cube
+fn parse_cells(initial_state: &str) -> Result<grid::Grid<bool>, AocError> {
+    grid::Grid::parse(DAY, initial_state, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Grid {
@@ -10,27 +24,26 @@ pub struct Grid {
 }
 
 impl Grid {
-    pub fn new(initial_state: &str, num_dimensions: usize, survive: Range<usize>, revive: Range<usize>) -> Grid {
+    pub fn new(initial_state: &str, num_dimensions: usize, survive: Range<usize>, revive: Range<usize>) -> Result<Grid, AocError> {
         if num_dimensions < 2 {
-            panic!("num_dimensions must be >= 2");
-        }
-        let mut grid: HashSet<Vec<isize>> = HashSet::new();
-        for (y, row) in initial_state.trim().split('\n').enumerate() {
-            for (x, chr) in row.chars().enumerate() {
-                if chr == '#' {
-                    let mut vec: Vec<isize> = vec![0_isize; num_dimensions];
-                    vec[0] = x as isize;
-                    vec[1] = y as isize;
-                    grid.insert(vec);
-                }
-            }
+            return Err(AocError::no_solution(DAY, "The grid needs at least 2 dimensions"));
         }
-        Grid {
-            active_cells: grid,
+        let cells = parse_cells(initial_state)?;
+        let active_cells = cells.positions()
+            .filter(|&position| cells[position])
+            .map(|(x, y)| {
+                let mut vec: Vec<isize> = vec![0_isize; num_dimensions];
+                vec[0] = x as isize;
+                vec[1] = y as isize;
+                vec
+            })
+            .collect();
+        Ok(Grid {
+            active_cells,
             survive,
             revive,
             dimensions: num_dimensions,
-        }
+        })
     }
 
     pub fn tick_n_times(&mut self, count: usize) {
@@ -95,10 +108,41 @@ impl Grid {
     }
 }
 
+pub struct Day17 {
+    input: String,
+}
+
+impl Solver for Day17 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day17 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(self.count_after_boot(3)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(self.count_after_boot(4)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
+impl Day17 {
+    fn count_after_boot(&self, num_dimensions: usize) -> Result<usize, AocError> {
+        let mut grid = Grid::new(&self.input, num_dimensions, 2..4, 3..4)?;
+        grid.tick_n_times(6);
+        Ok(grid.count_active_cells())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
     use crate::day17b::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_grid_init() {
@@ -110,7 +154,7 @@ mod tests {
         expected.insert(vec![2, 2, 0]);
         let grid = Grid::new(r".#.
 ..#
-###", 3, 2..4, 3..4);
+###", 3, 2..4, 3..4).unwrap();
         assert_eq!(expected, grid.active_cells);
     }
 
@@ -119,7 +163,7 @@ mod tests {
         let input = r".#.
 ..#
 ###";
-        let mut grid = Grid::new(input, 3, 2..4, 3..4);
+        let mut grid = Grid::new(input, 3, 2..4, 3..4).unwrap();
         println!("{:?}", grid);
         grid.tick_n_times(6);
         assert_eq!(112, grid.active_cells.len());
@@ -128,7 +172,7 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day17.txt").unwrap();
-        let mut grid = Grid::new(&input, 3, 2..4, 3..4);
+        let mut grid = Grid::new(&input, 3, 2..4, 3..4).unwrap();
         grid.tick_n_times(6);
         println!("active cells: {}", grid.count_active_cells());
     }
@@ -138,7 +182,7 @@ mod tests {
         let input = r".#.
 ..#
 ###";
-        let mut grid = Grid::new(input, 4, 2..4, 3..4);
+        let mut grid = Grid::new(input, 4, 2..4, 3..4).unwrap();
         grid.tick_n_times(6);
         assert_eq!(848, grid.count_active_cells());
     }
@@ -146,9 +190,14 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day17.txt").unwrap();
-        let mut grid = Grid::new(&input, 4, 2..4, 3..4);
+        let mut grid = Grid::new(&input, 4, 2..4, 3..4).unwrap();
         grid.tick_n_times(6);
         println!("active cells: {}", grid.count_active_cells());
     }
-}
 
+    #[test]
+    fn test_invalid_grid() {
+        assert_eq!(Err(AocError::parse(17, 2, 2, "Unknown character 'o'")), Grid::new(".#.\n.o#", 3, 2..4, 3..4));
+        assert!(Grid::new(".#.", 1, 2..4, 3..4).is_err());
+    }
+}
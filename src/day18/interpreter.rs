@@ -1,6 +1,9 @@
 use crate::day18::token::Token;
 use crate::day18::token::Type;
 use crate::day18::lexer::Lexer;
+use crate::error::AocError;
+
+const DAY: u8 = 18;
 
 /** Based on https://ruslanspivak.com/lsbasi-part1/
 */
@@ -22,39 +25,48 @@ impl Interpreter {
         interpreter
     }
 
+    /// Evaluates the whole expression, which must not be followed by anything else
+    pub fn evaluate(&mut self) -> Result<isize, AocError> {
+        let result = self.term()?;
+        match self.current_token {
+            None => Ok(result),
+            Some(token) => Err(AocError::parse(DAY, 1, token.column, format!("Unexpected {}", token.token_type))),
+        }
+    }
+
     /**
     term : expr ( (MUL|DIV) expr )* <br />
     expr : factor ( (PLUS|MINUS) factor )* <br />
     factor : INTEGER | LPAREN term RPAREN
     */
-    pub fn term(&mut self) -> Option<isize> {
+    pub fn term(&mut self) -> Result<isize, AocError> {
         let expr = match self.l2r {
             true => { Interpreter::factor }
             false => { Interpreter::expr }
         };
-        let mut result = self.expr();
+        let mut result = self.expr()?;
         while let Some(token) = self.current_token {
             match token.token_type {
                 Type::MUL => {
-                    self.eat(Type::MUL);
-                    result = Some(result? * expr(self)?);
+                    self.eat(Type::MUL)?;
+                    result = calculate(token, result, expr(self)?)?;
                 }
                 Type::DIV => {
-                    self.eat(Type::DIV);
-                    result = Some(result? / expr(self)?);
+                    self.eat(Type::DIV)?;
+                    result = calculate(token, result, expr(self)?)?;
                 }
                 Type::PLUS => {
-                    self.eat(Type::PLUS);
-                    result = Some(result? + self.factor()?);
+                    self.eat(Type::PLUS)?;
+                    result = calculate(token, result, self.factor()?)?;
                 }
                 Type::MINUS => {
-                    self.eat(Type::MINUS);
-                    result = Some(result? - self.factor()?);
+                    self.eat(Type::MINUS)?;
+                    result = calculate(token, result, self.factor()?)?;
                 }
                 _ => { break; }
             }
         }
-        result
+        Ok(result)
     }
 
     /**
@@ -62,94 +74,112 @@ impl Interpreter {
     expr : factor ( (PLUS|MINUS) factor )* <br />
     factor : INTEGER | LPAREN term RPAREN
     */
-    fn expr(&mut self) -> Option<isize> {
-        let mut result = self.factor();
+    fn expr(&mut self) -> Result<isize, AocError> {
+        let mut result = self.factor()?;
         while let Some(token) = self.current_token {
             match token.token_type {
                 Type::PLUS => {
-                    self.eat(Type::PLUS);
-                    result = Some(result? + self.factor()?);
+                    self.eat(Type::PLUS)?;
+                    result = calculate(token, result, self.factor()?)?;
                 }
                 Type::MINUS => {
-                    self.eat(Type::MINUS);
-                    result = Some(result? - self.factor()?);
+                    self.eat(Type::MINUS)?;
+                    result = calculate(token, result, self.factor()?)?;
                 }
                 _ => { break; }
             }
         }
-        result
+        Ok(result)
     }
 
-    fn factor(&mut self) -> Option<isize> {
-        let token = self.current_token?;
+    fn factor(&mut self) -> Result<isize, AocError> {
+        let token = self.current_token
+            .ok_or_else(|| AocError::parse(DAY, 1, self.lexer.column(), "Unexpected end of expression"))?;
         match token.token_type {
             Type::INTEGER => {
-                self.eat(Type::INTEGER);
-                token.token_value
+                self.eat(Type::INTEGER)?;
+                token.token_value.ok_or_else(|| AocError::parse(DAY, 1, token.column, "Invalid number"))
             }
             Type::LPAREN => {
-                self.eat(Type::LPAREN);
+                self.eat(Type::LPAREN)?;
                 let result = self.term();
-                self.eat(Type::RPAREN);
+                self.eat(Type::RPAREN)?;
                 result
             }
-            _ => { panic!() }
+            _ => { Err(AocError::parse(DAY, 1, token.column, format!("Expected number or '(', found {}", token.token_type))) }
         }
     }
 
-    fn eat(&mut self, expected_type: Type) {
-        if self.current_token.is_none() || self.current_token.unwrap().token_type != expected_type {
-            panic!("Unexpected token type");
+    fn eat(&mut self, expected_type: Type) -> Result<(), AocError> {
+        match self.current_token {
+            Some(token) if token.token_type == expected_type => {
+                self.current_token = self.lexer.get_next_token();
+                Ok(())
+            }
+            Some(token) => Err(AocError::parse(DAY, 1, token.column, format!("Expected {}, found {}", expected_type, token.token_type))),
+            None => Err(AocError::parse(DAY, 1, self.lexer.column(), format!("Expected {}, found end of expression", expected_type))),
         }
-        self.current_token = self.lexer.get_next_token();
     }
 }
 
+/// Applies the operator `token` to both operands, failing on overflow or division by zero
+fn calculate(token: Token, lhs: isize, rhs: isize) -> Result<isize, AocError> {
+    let result = match token.token_type {
+        Type::MUL => lhs.checked_mul(rhs),
+        Type::DIV => lhs.checked_div(rhs),
+        Type::PLUS => lhs.checked_add(rhs),
+        Type::MINUS => lhs.checked_sub(rhs),
+        _ => None,
+    };
+    result.ok_or_else(|| AocError::parse(DAY, 1, token.column, format!("Cannot apply {} to {} and {}", token.token_type, lhs, rhs)))
+}
+
 
 #[cfg(test)]
 mod tests {
     use crate::day18::interpreter::Interpreter;
     use crate::day18::lexer::Lexer;
+    use crate::error::AocError;
 
     #[test]
     fn test_plus_minus_two_terms() {
-        assert_eq!(Some(8), Interpreter::new(Lexer::new("3+5"), true).expr());
-        assert_eq!(Some(8), Interpreter::new(Lexer::new("13-5"), true).expr());
+        assert_eq!(Ok(8), Interpreter::new(Lexer::new("3+5"), true).expr());
+        assert_eq!(Ok(8), Interpreter::new(Lexer::new("13-5"), true).expr());
     }
 
     #[test]
     fn test_plus_minus_multiple_terms() {
-        assert_eq!(Some(8), Interpreter::new(Lexer::new("1+2+3+2"), true).expr());
-        assert_eq!(Some(5), Interpreter::new(Lexer::new("10 + 1 + 2 - 3 + 4 + 6 - 15"), true).expr());
+        assert_eq!(Ok(8), Interpreter::new(Lexer::new("1+2+3+2"), true).expr());
+        assert_eq!(Ok(5), Interpreter::new(Lexer::new("10 + 1 + 2 - 3 + 4 + 6 - 15"), true).expr());
     }
 
     #[test]
     fn test_mul_div() {
-        assert_eq!(Some(14), Interpreter::new(Lexer::new("7 * 4 / 2"), true).term());
+        assert_eq!(Ok(14), Interpreter::new(Lexer::new("7 * 4 / 2"), true).term());
     }
 
     #[test]
     fn test_operations_l2r() {
-        assert_eq!(Some(71), Interpreter::new(Lexer::new("1 + 2 * 3 + 4 * 5 + 6"), true).term());
+        assert_eq!(Ok(71), Interpreter::new(Lexer::new("1 + 2 * 3 + 4 * 5 + 6"), true).term());
     }
 
 
     #[test]
     fn test_parenthesis_l2r() {
-        assert_eq!(Some(51), Interpreter::new(Lexer::new("1 + (2 * 3) + (4 * (5 + 6))"), true).term());
-        assert_eq!(Some(26), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), true).term());
-        assert_eq!(Some(437), Interpreter::new(Lexer::new("5 + (8 * 3 + 9 + 3 * 4 * 3)"), true).term());
-        assert_eq!(Some(12240), Interpreter::new(Lexer::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), true).term());
-        assert_eq!(Some(13632), Interpreter::new(Lexer::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), true).term());
+        assert_eq!(Ok(51), Interpreter::new(Lexer::new("1 + (2 * 3) + (4 * (5 + 6))"), true).term());
+        assert_eq!(Ok(26), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), true).term());
+        assert_eq!(Ok(437), Interpreter::new(Lexer::new("5 + (8 * 3 + 9 + 3 * 4 * 3)"), true).term());
+        assert_eq!(Ok(12240), Interpreter::new(Lexer::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), true).term());
+        assert_eq!(Ok(13632), Interpreter::new(Lexer::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), true).term());
     }
 
     #[test]
     fn test_day18_part2_examples() {
-        assert_eq!(Some(51), Interpreter::new(Lexer::new("1 + (2 * 3) + (4 * (5 + 6))"), false).term());
-        assert_eq!(Some(46), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), false).term());
-        assert_eq!(Some(1445), Interpreter::new(Lexer::new("5 + (8 * 3 + 9 + 3 * 4 * 3)"), false).term());
-        assert_eq!(Some(669060), Interpreter::new(Lexer::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), false).term());
-        assert_eq!(Some(23340), Interpreter::new(Lexer::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), false).term());
+        assert_eq!(Ok(51), Interpreter::new(Lexer::new("1 + (2 * 3) + (4 * (5 + 6))"), false).term());
+        assert_eq!(Ok(46), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), false).term());
+        assert_eq!(Ok(1445), Interpreter::new(Lexer::new("5 + (8 * 3 + 9 + 3 * 4 * 3)"), false).term());
+        assert_eq!(Ok(669060), Interpreter::new(Lexer::new("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), false).term());
+        assert_eq!(Ok(23340), Interpreter::new(Lexer::new("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), false).term());
     }
 
     #[test]
@@ -171,4 +201,12 @@ mod tests {
             .sum();
         println!("{}", sum);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert_eq!(Err(AocError::parse(18, 1, 5, "Expected number or '(', found character 'x'")), Interpreter::new(Lexer::new("3 + x"), true).term());
+        assert_eq!(Err(AocError::parse(18, 1, 7, "Expected ')', found end of expression")), Interpreter::new(Lexer::new("(3 + 4"), true).term());
+        assert_eq!(Err(AocError::parse(18, 1, 3, "Cannot apply '/' to 3 and 0")), Interpreter::new(Lexer::new("3 / 0"), true).term());
+        assert_eq!(Err(AocError::parse(18, 1, 7, "Unexpected ')'")), Interpreter::new(Lexer::new("3 + 4 )"), true).evaluate());
+    }
+}
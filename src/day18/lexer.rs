@@ -40,16 +40,23 @@ impl Lexer {
         result.parse().ok()
     }
 
+    /// 1-based position of the current character, or one past the end of the input
+    pub fn column(&self) -> usize {
+        self.pos
+    }
+
     pub fn get_next_token(&mut self) -> Option<Token> {
         while let Some(current_char) = self.current_char {
             if current_char.is_whitespace() {
                 self.advance();
                 continue;
             }
+            let column = self.pos;
             if current_char.is_numeric() {
                 return Some(Token {
                     token_type: Type::INTEGER,
                     token_value: self.integer(),
+                    column,
                 });
             }
             if current_char == '+' {
@@ -57,6 +64,7 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::PLUS,
                     token_value: None,
+                    column,
                 });
             }
             if current_char == '-' {
@@ -64,6 +72,7 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::MINUS,
                     token_value: None,
+                    column,
                 });
             }
             if current_char == '*' {
@@ -71,6 +80,7 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::MUL,
                     token_value: None,
+                    column,
                 });
             }
             if current_char == '/' {
@@ -78,6 +88,7 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::DIV,
                     token_value: None,
+                    column,
                 });
             }
             if current_char == '(' {
@@ -85,6 +96,7 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::LPAREN,
                     token_value: None,
+                    column,
                 });
             }
             if current_char == ')' {
@@ -92,9 +104,15 @@ impl Lexer {
                 return Some(Token {
                     token_type: Type::RPAREN,
                     token_value: None,
+                    column,
                 });
             }
-            panic!(format!("Illegal character: '{}'", current_char));
+            self.advance();
+            return Some(Token {
+                token_type: Type::ILLEGAL(current_char),
+                token_value: None,
+                column,
+            });
         }
         None
     }
@@ -1,3 +1,45 @@
 pub mod token;
 pub mod interpreter;
 pub mod lexer;
+
+use crate::error::AocError;
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+use interpreter::Interpreter;
+use lexer::Lexer;
+
+const DAY: u8 = 18;
+
+pub struct Day18 {
+    input: String,
+}
+
+impl Day18 {
+    fn get_sum(&self, l2r: bool) -> Result<Answer, AocError> {
+        let mut sum: isize = 0;
+        for line in lines(&self.input) {
+            let value = Interpreter::new(Lexer::new(line), l2r).evaluate()
+                .map_err(|e| e.relative_to(&self.input, line))?;
+            sum = sum.checked_add(value).ok_or_else(|| AocError::no_solution(DAY, "The sum overflows"))?;
+        }
+        Ok(sum.into())
+    }
+}
+
+impl Solver for Day18 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day18 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        self.get_sum(true)
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        self.get_sum(false)
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Type {
     INTEGER,
@@ -7,10 +9,28 @@ pub enum Type {
     DIV,
     LPAREN,
     RPAREN,
+    ILLEGAL(char),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::INTEGER => write!(f, "number"),
+            Type::PLUS => write!(f, "'+'"),
+            Type::MINUS => write!(f, "'-'"),
+            Type::MUL => write!(f, "'*'"),
+            Type::DIV => write!(f, "'/'"),
+            Type::LPAREN => write!(f, "'('"),
+            Type::RPAREN => write!(f, "')'"),
+            Type::ILLEGAL(chr) => write!(f, "character '{}'", chr),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct Token {
     pub token_type: Type,
     pub token_value: Option<isize>,
+    /// 1-based position of the first character of the token
+    pub column: usize,
 }
\ No newline at end of file
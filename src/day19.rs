@@ -1,29 +1,60 @@
 use std::collections::HashMap;
 use pcre2::bytes::Regex;
+use crate::error::{parse_fragment, AocError};
+use crate::input::{blocks, lines};
+use crate::solver::{Answer, Solver};
 
-fn get_rules(input: &str) -> Option<HashMap<u16, &str>> {
-    let rules = input.split_once("\n\n")?.0;
+const DAY: u8 = 19;
+
+/// Splits the input into the rules and the messages section
+fn split_sections(input: &str) -> Result<(&str, &str), AocError> {
+    let mut sections = blocks(input);
+    sections.next().zip(sections.next())
+        .ok_or_else(|| AocError::parse_at(DAY, input, &input[input.len()..], "Expected rules and messages separated by a blank line"))
+}
+
+fn get_rules(input: &str) -> Result<HashMap<u16, &str>, AocError> {
+    let rules = split_sections(input)?.0;
     let mut rules_map: HashMap<u16, &str> = HashMap::new();
-    for rule in rules.trim().lines() {
-        let (id, rule) = rule.trim().split_once(": ")?;
-        let id: u16 = id.parse().ok()?;
+    for rule in lines(rules) {
+        let (id, rule) = rule.split_once(": ")
+            .ok_or_else(|| AocError::parse_at(DAY, input, rule, format!("Expected '<id>: <rule>', found '{}'", rule)))?;
+        let id: u16 = parse_fragment(DAY, input, id)?;
+        if let Some(segment) = rule.split_whitespace()
+            .find(|&segment| segment != "|" && segment.parse::<u16>().is_err() && !is_literal(segment)) {
+            return Err(AocError::parse_at(DAY, input, segment, format!("Expected a rule ID, '|' or a quoted character, found '{}'", segment)));
+        }
         rules_map.insert(id, rule);
     }
-    Some(rules_map)
+    Ok(rules_map)
 }
 
+fn is_literal(segment: &str) -> bool {
+    segment.len() > 2 && segment.starts_with('"') && segment.ends_with('"')
+        && segment[1..segment.len() - 1].chars().all(char::is_alphanumeric)
+}
+
+fn get_rule<'a>(rules: &HashMap<u16, &'a str>, rule_id: u16) -> Result<&'a str, AocError> {
+    rules.get(&rule_id).copied().ok_or_else(|| AocError::no_solution(DAY, format!("Rule {} is not defined", rule_id)))
+}
 
-fn resolve_subrules(rule: &str, rules: &HashMap<u16, &str>, special: bool) -> Option<String> {
+/// Resolves the rule, `path` holds the IDs of the rules that are being resolved, so that cycles are reported
+fn resolve_subrules(rule: &str, rules: &HashMap<u16, &str>, special: bool, path: &mut Vec<u16>) -> Result<String, AocError> {
     let mut working_copy = format!(" {} ", rule);
     for segment in rule.split_whitespace() {
         if let Ok(rule_id) = segment.parse::<u16>() {
-            let mut subrule = resolve_subrules(rules.get(&rule_id)?, rules, special)?;
+            if let Some(start) = path.iter().position(|&id| id == rule_id) {
+                let cycle: Vec<String> = path[start..].iter().chain(std::iter::once(&rule_id)).map(u16::to_string).collect();
+                return Err(AocError::no_solution(DAY, format!("Rule {} refers to itself: {}", rule_id, cycle.join(" -> "))));
+            }
+            path.push(rule_id);
+            let mut subrule = resolve_subrules(get_rule(rules, rule_id)?, rules, special, path)?;
             if special {
                 if rule_id == 8 {
                     subrule = format!(" (?:{})+ ", subrule);
                 } else if rule_id == 11 {
-                    let sub42 = resolve_subrules(rules.get(&42_u16)?, rules, special)?;
-                    let sub31 = resolve_subrules(rules.get(&31_u16)?, rules, special)?;
+                    let sub42 = resolve_subrules(get_rule(rules, 42)?, rules, special, path)?;
+                    let sub31 = resolve_subrules(get_rule(rules, 31)?, rules, special, path)?;
                     subrule = format!(" (?'eleven' (?:{}) (?P>eleven)? (?:{}) )", sub42, sub31);
                 } else if subrule.len() > 1 {
                     subrule = format!(" (?:{}) ", subrule);
@@ -35,34 +66,65 @@ fn resolve_subrules(rule: &str, rules: &HashMap<u16, &str>, special: bool) -> Op
             } else {
                 subrule = format!(" {} ", subrule);
             }
-
+            path.pop();
 
             working_copy = working_copy.replace(&format!(" {} ", segment),
                                                 &subrule);
         }
     }
-    Some(working_copy.replace(" ", "").replace("\"", ""))
+    Ok(working_copy.replace(" ", "").replace("\"", ""))
 }
 
-fn get_combined_rule(rules: &HashMap<u16, &str>, special: bool) -> Option<String> {
-    Some(format!("^{}$", resolve_subrules(rules.get(&0_u16).unwrap(), &rules, special)?))
+fn get_combined_rule(rules: &HashMap<u16, &str>, special: bool) -> Result<String, AocError> {
+    Ok(format!("^{}$", resolve_subrules(get_rule(rules, 0)?, rules, special, &mut vec![0])?))
 }
 
-fn get_messages(input: &str) -> Option<Vec<&str>> {
-    Some(input.split_once("\n\n")?.1.lines().collect())
+fn get_messages(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(lines(split_sections(input)?.1).collect())
 }
 
-pub fn get_number_of_matching_messages(input: &str, special: bool) -> Option<usize> {
+pub fn get_number_of_matching_messages(input: &str, special: bool) -> Result<usize, AocError> {
     let messages = get_messages(input)?;
     let rules = get_rules(input)?;
     let combined_rule = get_combined_rule(&rules, special)?;
-    let re: Regex = Regex::new(&combined_rule).unwrap();
-    Some(messages.iter().filter(|&msg| re.is_match(msg.as_bytes()).unwrap()).count())
+    let re: Regex = Regex::new(&combined_rule)
+        .map_err(|e| AocError::no_solution(DAY, format!("The rules do not form a valid pattern: {}", e)))?;
+    let mut count = 0;
+    for msg in messages {
+        if re.is_match(msg.as_bytes())
+            .map_err(|e| AocError::no_solution(DAY, format!("Error matching '{}': {}", msg, e)))? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+pub struct Day19 {
+    input: String,
+}
+
+impl Solver for Day19 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day19 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_number_of_matching_messages(&self.input, false)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_number_of_matching_messages(&self.input, true)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day19::{get_rules, resolve_subrules, get_combined_rule, get_number_of_matching_messages};
+    use crate::error::AocError;
     use std::collections::HashMap;
 
     fn get_example1() -> String {
@@ -141,12 +203,12 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
         expected.insert(3, "4 5 | 5 4");
         expected.insert(4, r#""a""#);
         expected.insert(5, r#""b""#);
-        assert_eq!(Some(expected), get_rules(&input));
+        assert_eq!(Ok(expected), get_rules(&input));
     }
 
     #[test]
     fn test_resolve_subrules_simple() {
-        assert_eq!(Some(String::from("a")), resolve_subrules(r#" "a" "#, &HashMap::new(), false));
+        assert_eq!(Ok(String::from("a")), resolve_subrules(r#" "a" "#, &HashMap::new(), false, &mut Vec::new()));
     }
 
     #[test]
@@ -159,7 +221,7 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
     #[test]
     fn test_example1() {
         let input = get_example1();
-        assert_eq!(Some(2), get_number_of_matching_messages(&input, false));
+        assert_eq!(Ok(2), get_number_of_matching_messages(&input, false));
     }
 
     #[test]
@@ -171,8 +233,8 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
     #[test]
     fn test_example2() {
         let input = get_example2();
-        assert_eq!(Some(3), get_number_of_matching_messages(&input, false));
-        assert_eq!(Some(12), get_number_of_matching_messages(&input, true));
+        assert_eq!(Ok(3), get_number_of_matching_messages(&input, false));
+        assert_eq!(Ok(12), get_number_of_matching_messages(&input, true));
     }
 
     #[test]
@@ -194,4 +256,22 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#)
         println!("{}", get_combined_rule(&rules, true).unwrap());
         println!("{:?}", get_number_of_matching_messages(&input, true));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(Err(AocError::parse(19, 2, 4, "Expected a rule ID, '|' or a quoted character, found 'a'")),
+                   get_number_of_matching_messages("0: 1 1\n1: a\n\naa", false));
+        assert_eq!(Err(AocError::no_solution(19, "Rule 2 is not defined")),
+                   get_number_of_matching_messages("0: 1 2\n1: \"a\"\n\naa", false));
+        assert_eq!(Err(AocError::parse(19, 2, 7, "Expected rules and messages separated by a blank line")),
+                   get_number_of_matching_messages("0: 1 1\n1: \"a\"", false));
+    }
+
+    #[test]
+    fn test_cyclic_rules() {
+        assert_eq!(Err(AocError::no_solution(19, "Rule 0 refers to itself: 0 -> 1 -> 0")),
+                   get_number_of_matching_messages("0: 1\n1: 0\n\na", false));
+        assert_eq!(Err(AocError::no_solution(19, "Rule 2 refers to itself: 2 -> 2")),
+                   get_number_of_matching_messages("0: 1 2\n1: \"a\"\n2: 1 | 1 2\n\naa", true));
+    }
+}
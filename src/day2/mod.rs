@@ -0,0 +1,144 @@
+pub mod policy;
+pub mod report;
+
+use regex::Regex;
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+use policy::{CountPolicy, PasswordPolicy, PositionPolicy};
+
+const DAY: u8 = 2;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(r"(\d+)-(\d+) (\w): (\w+)").unwrap();
+}
+
+/// Line of the password database. The meaning of the two numbers and the letter depends on the policy
+pub struct Entry<'a> {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+    pub password: &'a str,
+}
+
+/// Parses a line of the password database. `line` has to be a slice of `input`
+fn parse_entry<'a>(input: &str, line: &'a str) -> Result<Entry<'a>, AocError> {
+    let line = line.trim();
+    let segments = RE.captures(line).ok_or_else(|| AocError::parse_at(DAY, input, line,
+        format!("Expected '<min>-<max> <letter>: <password>', found '{}'", line)))?;
+    let min = segments.get(1).unwrap().as_str();
+    let max = segments.get(2).unwrap().as_str();
+    let entry = Entry {
+        first: parse_fragment(DAY, input, min)?,
+        second: parse_fragment(DAY, input, max)?,
+        letter: segments[3].chars().next().unwrap(),
+        password: segments.get(4).unwrap().as_str(),
+    };
+    if entry.first > entry.second {
+        return Err(AocError::parse_at(DAY, input, max, format!("{} is smaller than {}", entry.second, entry.first)));
+    }
+    Ok(entry)
+}
+
+/// Checks a single line of the password database against the old policy
+pub fn is_valid(input: &str) -> Result<bool, AocError> {
+    Ok(CountPolicy.is_valid(&parse_entry(input, input)?))
+}
+
+/// Checks a single line of the password database against the new policy
+pub fn is_valid_new_policy(password: &str) -> Result<bool, AocError> {
+    Ok(PositionPolicy.is_valid(&parse_entry(password, password)?))
+}
+
+/// Counts the lines of the password database whose password satisfies the policy
+pub fn count_valid_entries(input: &str, policy: &dyn PasswordPolicy) -> Result<usize, AocError> {
+    let mut count = 0;
+    for line in lines(input) {
+        if policy.is_valid(&parse_entry(input, line)?) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+pub fn count_valid_passwords(input: &str) -> Result<usize, AocError> {
+    count_valid_entries(input, &CountPolicy)
+}
+
+pub fn count_valid_passwords_new_policy(input: &str) -> Result<usize, AocError> {
+    count_valid_entries(input, &PositionPolicy)
+}
+
+pub struct Day2 {
+    input: String,
+}
+
+impl Solver for Day2 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day2 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(count_valid_passwords(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(count_valid_passwords_new_policy(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert_eq!(true, is_valid("1-3 a: abcde").unwrap(), "1-3 a: abcde");
+        assert_eq!(false, is_valid("1-3 b: cdefg").unwrap(), "1-3 b: cdefg");
+        assert_eq!(true, is_valid("2-9 c: ccccccccc").unwrap(), "2-9 c: ccccccccc");
+    }
+
+
+    #[test]
+    fn test_part1() {
+        let input = std::fs::read_to_string("resources/day2.txt")
+            .expect("Error reading file to string");
+        println!("{}", count_valid_passwords(input.as_str()).unwrap());
+    }
+
+    #[test]
+    fn test_part2_examples() {
+        assert_eq!(true, is_valid_new_policy("1-3 a: abcde").unwrap(), "1-3 a: abcde");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = std::fs::read_to_string("resources/day2.txt")
+            .expect("Error reading file to string");
+        println!("{}", count_valid_passwords_new_policy(input.as_str()).unwrap());
+    }
+
+    #[test]
+    fn test_count_with_custom_policy() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+        let policy = policy::parse_policy("all(count, not(positions))").unwrap();
+        assert_eq!(1, count_valid_entries(input, policy.as_ref()).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_line() {
+        assert_eq!(Err(AocError::parse(2, 2, 1, "Expected '<min>-<max> <letter>: <password>', found '1-3 b cdefg'")),
+                   count_valid_passwords("1-3 a: abcde\n1-3 b cdefg\n"));
+        assert_eq!(Err(AocError::parse(2, 1, 3, "0 is smaller than 1")), count_valid_passwords("1-0 a: abcde"));
+    }
+
+    #[test]
+    fn test_zero_minimum() {
+        assert_eq!(2, count_valid_passwords("0-3 a: bbb\n1-3 a: abc\n").unwrap());
+        assert_eq!(0, count_valid_passwords_new_policy("0-3 a: bba\n").unwrap());
+    }
+}
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use crate::day2::Entry;
+
+/// Rule that decides whether the password of an entry is valid. The policy decides how the numbers and the letter of
+/// the entry are interpreted. Policies print themselves in the syntax understood by `parse_policy`
+pub trait PasswordPolicy: fmt::Display {
+    /// Returns why the password violates the policy, or `None` if it is valid
+    fn violation(&self, entry: &Entry) -> Option<String>;
+
+    fn is_valid(&self, entry: &Entry) -> bool {
+        self.violation(entry).is_none()
+    }
+}
+
+/// The letter has to occur at least `first` and at most `second` times (the policy of the sled rental place)
+pub struct CountPolicy;
+
+/// Exactly one of the 1-based positions `first` and `second` has to hold the letter (the Official Toboggan Corporate
+/// Policy)
+pub struct PositionPolicy;
+
+/// The password needs at least the given number of characters
+pub struct MinLength(pub usize);
+
+/// All policies have to be satisfied
+pub struct AllOf(pub Vec<Box<dyn PasswordPolicy>>);
+
+/// At least one of the policies has to be satisfied
+pub struct AnyOf(pub Vec<Box<dyn PasswordPolicy>>);
+
+/// The policy must not be satisfied
+pub struct Not(pub Box<dyn PasswordPolicy>);
+
+impl PasswordPolicy for CountPolicy {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        let count = entry.password.chars().filter(|&c| c == entry.letter).count();
+        if (entry.first..=entry.second).contains(&count) {
+            None
+        } else {
+            Some(format!("'{}' occurs {} times, expected {} to {}", entry.letter, count, entry.first, entry.second))
+        }
+    }
+}
+
+impl PasswordPolicy for PositionPolicy {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        if entry.first == 0 {
+            return Some(String::from("Positions start at 1"));
+        }
+        let is_match = |position: usize| {
+            position.checked_sub(1).and_then(|idx| entry.password.chars().nth(idx)) == Some(entry.letter)
+        };
+        match (is_match(entry.first), is_match(entry.second)) {
+            (true, true) => Some(format!("'{}' is at both positions {} and {}", entry.letter, entry.first, entry.second)),
+            (false, false) => Some(format!("'{}' is at neither position {} nor {}", entry.letter, entry.first, entry.second)),
+            _ => None,
+        }
+    }
+}
+
+impl PasswordPolicy for MinLength {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        let length = entry.password.chars().count();
+        if length >= self.0 {
+            None
+        } else {
+            Some(format!("The password has {} characters, expected at least {}", length, self.0))
+        }
+    }
+}
+
+impl PasswordPolicy for AllOf {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        self.0.iter().find_map(|policy| policy.violation(entry))
+    }
+}
+
+impl PasswordPolicy for AnyOf {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        // stops at the first satisfied policy
+        let violations = self.0.iter().map(|policy| policy.violation(entry)).collect::<Option<Vec<String>>>()?;
+        if violations.is_empty() {
+            Some(String::from("No policy to satisfy"))
+        } else {
+            Some(violations.join(" and "))
+        }
+    }
+}
+
+impl PasswordPolicy for Not {
+    fn violation(&self, entry: &Entry) -> Option<String> {
+        match self.0.violation(entry) {
+            Some(_) => None,
+            None => Some(format!("The password satisfies {}", self.0)),
+        }
+    }
+}
+
+impl fmt::Display for CountPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "count")
+    }
+}
+
+impl fmt::Display for PositionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "positions")
+    }
+}
+
+impl fmt::Display for MinLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "min_length({})", self.0)
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, name: &str, policies: &[Box<dyn PasswordPolicy>]) -> fmt::Result {
+    let policies: Vec<String> = policies.iter().map(|policy| policy.to_string()).collect();
+    write!(f, "{}({})", name, policies.join(", "))
+}
+
+impl fmt::Display for AllOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_list(f, "all", &self.0)
+    }
+}
+
+impl fmt::Display for AnyOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_list(f, "any", &self.0)
+    }
+}
+
+impl fmt::Display for Not {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not({})", self.0)
+    }
+}
+
+/// Parses a policy such as `all(count, not(positions), min_length(8))`. The built-in policies are `count` and
+/// `positions`, `min_length(<n>)` checks the length of the password, and `all(...)`, `any(...)` and `not(...)`
+/// combine other policies
+pub fn parse_policy(text: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+    let mut parser = Parser { text, rest: text };
+    let policy = parser.policy()?;
+    parser.skip_whitespace();
+    if !parser.rest.is_empty() {
+        return Err(parser.error(format!("Unexpected '{}'", parser.rest)));
+    }
+    Ok(policy)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: String) -> String {
+        let column = self.text[..self.text.len() - self.rest.len()].chars().count() + 1;
+        format!("Column {}: {}", column, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(format!("Expected '{}'", expected))),
+        }
+    }
+
+    fn token(&mut self, is_part: fn(char) -> bool, name: &str) -> Result<&str, String> {
+        self.skip_whitespace();
+        let end = self.rest.find(|c: char| !is_part(c)).unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err(self.error(format!("Expected {}", name)));
+        }
+        let (token, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok(token)
+    }
+
+    fn policy(&mut self) -> Result<Box<dyn PasswordPolicy>, String> {
+        let start = self.rest.trim_start();
+        let name = self.token(|c| c.is_ascii_alphanumeric() || c == '_', "a policy")?.to_owned();
+        match name.as_str() {
+            "count" => Ok(Box::new(CountPolicy)),
+            "positions" => Ok(Box::new(PositionPolicy)),
+            "min_length" => {
+                self.expect('(')?;
+                let length = self.token(|c| c.is_ascii_digit(), "a number")?.to_owned();
+                let length = length.parse().map_err(|e| self.error(format!("Invalid length '{}': {}", length, e)))?;
+                self.expect(')')?;
+                Ok(Box::new(MinLength(length)))
+            }
+            "not" => {
+                self.expect('(')?;
+                let policy = self.policy()?;
+                self.expect(')')?;
+                Ok(Box::new(Not(policy)))
+            }
+            "all" | "any" => {
+                self.expect('(')?;
+                let mut policies = Vec::new();
+                self.skip_whitespace();
+                if !self.rest.starts_with(')') {
+                    policies.push(self.policy()?);
+                    while self.expect(',').is_ok() {
+                        policies.push(self.policy()?);
+                    }
+                }
+                self.expect(')')?;
+                Ok(if name == "all" { Box::new(AllOf(policies)) } else { Box::new(AnyOf(policies)) })
+            }
+            _ => {
+                self.rest = start;
+                Err(self.error(format!("Unknown policy '{}'", name)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(password: &str) -> Entry<'_> {
+        Entry { first: 1, second: 3, letter: 'a', password }
+    }
+
+    #[test]
+    fn test_builtin_policies() {
+        assert!(CountPolicy.is_valid(&entry("abcde")));
+        assert_eq!(Some(String::from("'a' occurs 4 times, expected 1 to 3")), CountPolicy.violation(&entry("aaaa")));
+        assert!(PositionPolicy.is_valid(&entry("abcde")));
+        assert_eq!(Some(String::from("'a' is at both positions 1 and 3")), PositionPolicy.violation(&entry("aaaa")));
+        assert_eq!(Some(String::from("'a' is at neither position 1 nor 3")), PositionPolicy.violation(&entry("b")));
+        assert_eq!(Some(String::from("Positions start at 1")), PositionPolicy.violation(&Entry { first: 0, ..entry("bba") }));
+        assert_eq!(Some(String::from("The password has 5 characters, expected at least 8")), MinLength(8).violation(&entry("abcde")));
+    }
+
+    #[test]
+    fn test_composite_policies() {
+        let policy = AllOf(vec![Box::new(CountPolicy), Box::new(Not(Box::new(PositionPolicy)))]);
+        assert!(policy.is_valid(&entry("aba")));
+        assert_eq!(Some(String::from("The password satisfies positions")), policy.violation(&entry("abcde")));
+        let policy = AnyOf(vec![Box::new(PositionPolicy), Box::new(MinLength(6))]);
+        assert!(policy.is_valid(&entry("bbbbbb")));
+        assert_eq!(Some(String::from("'a' is at neither position 1 nor 3 and The password has 1 characters, expected at least 6")),
+                   policy.violation(&entry("b")));
+        assert!(AllOf(Vec::new()).is_valid(&entry("")));
+        assert!(!AnyOf(Vec::new()).is_valid(&entry("")));
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let text = "all(count, not(positions), any(min_length(8), positions))";
+        assert_eq!(text, parse_policy(text).unwrap().to_string());
+        assert_eq!("all(count, positions)", parse_policy(" all ( count ,positions ) ").unwrap().to_string());
+        assert_eq!("any()", parse_policy("any()").unwrap().to_string());
+        assert!(!parse_policy("not(count)").unwrap().is_valid(&entry("abcde")));
+    }
+
+    #[test]
+    fn test_parse_invalid_policy() {
+        assert_eq!(Some(String::from("Column 5: Unknown policy 'size'")), parse_policy("all(size)").err());
+        assert_eq!(Some(String::from("Column 10: Expected ')'")), parse_policy("not(count").err());
+        assert_eq!(Some(String::from("Column 12: Expected a number")), parse_policy("min_length(x)").err());
+        assert_eq!(Some(String::from("Column 7: Unexpected 'positions'")), parse_policy("count positions").err());
+        assert_eq!(Some(String::from("Column 1: Expected a policy")), parse_policy("").err());
+    }
+}
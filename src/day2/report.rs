@@ -0,0 +1,121 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::day2::parse_entry;
+use crate::day2::policy::PasswordPolicy;
+use crate::error::{locate, AocError};
+use crate::input::lines;
+use crate::report::{write_csv_records, write_json_records};
+
+/// Outcome of checking one line of the password database
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LineStatus {
+    Valid,
+    /// The password violates the policy for the given reason
+    Invalid(String),
+    /// The line could not be parsed
+    Malformed(AocError),
+}
+
+/// Status of a single line of the password database, `line` is 1-based
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineReport {
+    pub line: usize,
+    pub text: String,
+    pub status: LineStatus,
+}
+
+/// Checks every line of the password database against the policy. Unlike `count_valid_entries`, malformed lines don't
+/// stop the check but are reported with their parse error
+pub fn check_lines(input: &str, policy: &dyn PasswordPolicy) -> Vec<LineReport> {
+    lines(input)
+        .map(|line| {
+            let status = match parse_entry(input, line) {
+                Ok(entry) => match policy.violation(&entry) {
+                    None => LineStatus::Valid,
+                    Some(reason) => LineStatus::Invalid(reason),
+                },
+                Err(e) => LineStatus::Malformed(e),
+            };
+            LineReport { line: locate(input, line).0, text: line.to_owned(), status }
+        })
+        .collect()
+}
+
+/// Flat representation of a `LineReport` for JSON and CSV output. `reason` is set unless the password is valid
+#[derive(Debug, Serialize)]
+struct LineRecord<'a> {
+    line: usize,
+    text: &'a str,
+    status: &'static str,
+    policy: String,
+    reason: Option<String>,
+}
+
+impl<'a> LineRecord<'a> {
+    fn new(report: &'a LineReport, policy: &dyn PasswordPolicy) -> Self {
+        let (status, reason) = match &report.status {
+            LineStatus::Valid => ("valid", None),
+            LineStatus::Invalid(reason) => ("invalid", Some(reason.clone())),
+            LineStatus::Malformed(e) => ("malformed", Some(e.to_string())),
+        };
+        LineRecord { line: report.line, text: &report.text, status, policy: policy.to_string(), reason }
+    }
+}
+
+pub fn write_json(reports: &[LineReport], policy: &dyn PasswordPolicy, writer: impl Write) -> Result<(), String> {
+    let records: Vec<LineRecord> = reports.iter().map(|report| LineRecord::new(report, policy)).collect();
+    write_json_records(&records, writer)
+}
+
+pub fn write_csv(reports: &[LineReport], policy: &dyn PasswordPolicy, writer: impl Write) -> Result<(), String> {
+    let records: Vec<LineRecord> = reports.iter().map(|report| LineRecord::new(report, policy)).collect();
+    write_csv_records(&records, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::policy::{CountPolicy, PositionPolicy};
+
+    const INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n\n2-1 c: ccccccccc";
+
+    #[test]
+    fn test_check_lines() {
+        assert_eq!(vec![
+            LineReport { line: 1, text: String::from("1-3 a: abcde"), status: LineStatus::Valid },
+            LineReport { line: 2, text: String::from("1-3 b: cdefg"),
+                status: LineStatus::Invalid(String::from("'b' occurs 0 times, expected 1 to 3")) },
+            LineReport { line: 3, text: String::new(),
+                status: LineStatus::Malformed(AocError::parse(2, 3, 1, "Expected '<min>-<max> <letter>: <password>', found ''")) },
+            LineReport { line: 4, text: String::from("2-1 c: ccccccccc"),
+                status: LineStatus::Malformed(AocError::parse(2, 4, 3, "1 is smaller than 2")) },
+        ], check_lines(INPUT, &CountPolicy));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut output = Vec::new();
+        write_csv(&check_lines("1-3 a: abcde\n1-3 b: cdefg\nx", &PositionPolicy), &PositionPolicy, &mut output).unwrap();
+        assert_eq!("line,text,status,policy,reason\n\
+                    1,1-3 a: abcde,valid,positions,\n\
+                    2,1-3 b: cdefg,invalid,positions,'b' is at neither position 1 nor 3\n\
+                    3,x,malformed,positions,\"Day 2, line 3, column 1: Expected '<min>-<max> <letter>: <password>', found 'x'\"\n",
+                   String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut output = Vec::new();
+        write_json(&check_lines(INPUT, &CountPolicy), &CountPolicy, &mut output).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(4, json.as_array().unwrap().len());
+        assert_eq!("valid", json[0]["status"]);
+        assert!(json[0]["reason"].is_null());
+        assert_eq!("count", json[1]["policy"]);
+        assert_eq!("'b' occurs 0 times, expected 1 to 3", json[1]["reason"]);
+        assert_eq!("malformed", json[3]["status"]);
+        assert_eq!("Day 2, line 4, column 3: 1 is smaller than 2", json[3]["reason"]);
+    }
+}
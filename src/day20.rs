@@ -1,4 +1,9 @@
-use std::convert::TryFrom;
+use crate::error::{parse_fragment, AocError};
+use crate::grid::Grid;
+use crate::input::blocks;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 20;
 
 pub enum Border {
     TOP,
@@ -7,34 +12,33 @@ pub enum Border {
     LEFT,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Tile {
     pub id: u16,
-    data: [bool; 100],
+    data: Grid<bool>,
 }
 
 impl Tile {
-    pub fn new(input: &str) -> Option<Tile> {
-        let (title, data) = input.trim().split_once(":\n")?;
-        let tile_id: u16 = title.trim().split_once(' ')?.1.parse().ok()?;
-        let mut bitmap = [false; 100];
-        let mut idx = 0;
-        for c in data.chars() {
-            match c {
-                '#' => {
-                    bitmap[idx] = true;
-                    idx += 1;
-                }
-                '.' => {
-                    bitmap[idx] = false;
-                    idx += 1
-                }
-                _ => {}
-            }
+    pub fn new(input: &str) -> Result<Tile, AocError> {
+        let input = input.trim_end();
+        let (title, cells) = input.split_once('\n').unwrap_or((input, &input[input.len()..]));
+        let tile_id = title.strip_prefix("Tile ").and_then(|t| t.strip_suffix(':'))
+            .ok_or_else(|| AocError::parse(DAY, 1, 1, format!("Expected 'Tile <id>:', found '{}'", title)))?;
+        let tile_id: u16 = parse_fragment(DAY, input, tile_id)?;
+        let data = Grid::parse(DAY, cells, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }).map_err(|e| e.relative_to(input, cells))?;
+        if data.width() != 10 {
+            return Err(AocError::parse(DAY, 2, 1, "Tiles have to be 10 by 10 cells"));
+        }
+        if data.height() != 10 {
+            return Err(AocError::parse(DAY, data.height().min(10) + 2, 1, "Tiles have to be 10 by 10 cells"));
         }
-        Some(Tile {
+        Ok(Tile {
             id: tile_id,
-            data: bitmap,
+            data,
         })
     }
 
@@ -59,91 +63,47 @@ impl Tile {
 
     pub fn aligns_directly(&self, other: &Tile, border: &Border) -> bool {
         match border {
-            Border::TOP => self.data[..10] == other.data[90..],
-            Border::RIGHT => {
-                self.data.iter().enumerate().filter(|&(k, _)| k % 10 == 9).map(|(_, &v)| v).collect::<Vec<bool>>()
-                    == other.data.iter().enumerate().filter(|&(k, _)| k % 10 == 0).map(|(_, &v)| v).collect::<Vec<bool>>()
-            }
-            Border::BOTTOM => self.data[90..] == other.data[..10],
-            Border::LEFT => {
-                self.data.iter().enumerate().filter(|&(k, _)| k % 10 == 0).map(|(_, &v)| v).collect::<Vec<bool>>()
-                    == other.data.iter().enumerate().filter(|&(k, _)| k % 10 == 9).map(|(_, &v)| v).collect::<Vec<bool>>()
-            }
+            Border::TOP => self.data.row(0) == other.data.row(9),
+            Border::RIGHT => self.data.column(9).eq(other.data.column(0)),
+            Border::BOTTOM => self.data.row(9) == other.data.row(0),
+            Border::LEFT => self.data.column(0).eq(other.data.column(9)),
         }
     }
 
     fn rotate(&mut self, degrees: i16) {
-        let cloned = self.data;
         let degrees = (degrees + 360) % 360;
-        match degrees {
-            0 => {}
-            90 => {
-                self.data = <[bool; 100]>::try_from(rotate(&cloned)).unwrap();
-                /*for (idx, &bit) in cloned.iter().enumerate() {
-                    let row = idx / 10;
-                    let col = idx % 10;
-                    self.data[col * 10 + (9 - row)] = bit;
-                }*/
-            }
-            180 => {
-                self.data = <[bool; 100]>::try_from(rotate(&rotate(&cloned))).unwrap();
-            }
-            270 => {
-                self.data = <[bool; 100]>::try_from(rotate(&rotate(&rotate(&cloned)))).unwrap();
+        if degrees % 90 == 0 {
+            for _ in 0..degrees / 90 {
+                self.data = self.data.rotate();
             }
-            _ => {}
         }
     }
 
     fn flip(&mut self, border: Border) {
-        self.data = <[bool; 100]>::try_from(flip(&self.data, border)).unwrap();
-    }
-}
-
-pub fn rotate<T: Copy>(data: &[T]) -> Vec<T> {
-    let width = (data.len() as f64).sqrt() as usize;
-    let mut res = vec![data[0]; data.len()];
-    for (idx, &bit) in data.iter().enumerate() {
-        let row = idx / width;
-        let col = idx % width;
-        res[col * width + (width - 1 - row)] = bit;
-    }
-    res
-}
-
-pub fn flip<T: Copy>(data: &[T], border: Border) -> Vec<T> {
-    let width = (data.len() as f64).sqrt() as usize;
-    let mut res = vec![data[0]; data.len()];
-    match border {
-        Border::TOP | Border::BOTTOM => {
-            for (idx, &bit) in data.iter().enumerate() {
-                let row = idx / width;
-                let col = idx % width;
-                res[(width - 1 - row) * width + col] = bit;
-            }
-        }
-        Border::RIGHT | Border::LEFT => {
-            for (idx, &bit) in data.iter().enumerate() {
-                let row = idx / width;
-                let col = idx % width;
-                res[row * width + (width - 1 - col)] = bit;
-            }
-        }
+        self.data = match border {
+            Border::TOP | Border::BOTTOM => self.data.flip_vertical(),
+            Border::RIGHT | Border::LEFT => self.data.flip_horizontal(),
+        };
     }
-    res
 }
 
-pub fn get_tiles(input: &str) -> Option<Vec<Tile>> {
+pub fn get_tiles(input: &str) -> Result<Vec<Tile>, AocError> {
     let mut res: Vec<Tile> = Vec::new();
-    for tile in input.trim().split("\n\n") {
-        res.push(Tile::new(tile)?);
+    for tile in blocks(input) {
+        res.push(Tile::new(tile).map_err(|e| e.relative_to(input, tile))?);
     }
-    Some(res)
+    Ok(res)
 }
 
-pub fn get_sorted_tiles(mut tiles: Vec<Tile>) -> Option<Vec<Tile>> {
+/// Arranges the tiles in a square, so that the borders of neighbouring tiles match
+pub fn get_sorted_tiles(mut tiles: Vec<Tile>) -> Result<Grid<Tile>, AocError> {
     let width = (tiles.len() as f64).sqrt() as usize;
+    if tiles.is_empty() || width * width != tiles.len() {
+        return Err(AocError::no_solution(DAY, format!("{} tiles can't be arranged in a square", tiles.len())));
+    }
     sort_tiles(&mut Vec::new(), &mut tiles, width)
+        .map(|tiles| Grid::new(width, tiles))
+        .ok_or_else(|| AocError::no_solution(DAY, "The tiles can't be arranged"))
 }
 
 pub fn sort_tiles(found: &mut Vec<Tile>, available: &mut Vec<Tile>, width: usize) -> Option<Vec<Tile>> {
@@ -160,7 +120,7 @@ pub fn sort_tiles(found: &mut Vec<Tile>, available: &mut Vec<Tile>, width: usize
                     for _vflip in 0..2 {
                         tile.flip(Border::LEFT);
 
-                        found.push(tile);
+                        found.push(tile.clone());
                         match sort_tiles(found, available, width) {
                             None => {
                                 // undo and continue
@@ -186,7 +146,7 @@ pub fn sort_tiles(found: &mut Vec<Tile>, available: &mut Vec<Tile>, width: usize
                     if found.len() / width == 0 ||
                         found.get(found.len() - width)?.aligns_directly(&tile, &Border::BOTTOM) {
                         // has no top neighbour or aligns directly
-                        found.push(tile);
+                        found.push(tile.clone());
                         match sort_tiles(found, available, width) {
                             None => {
                                 // undo and continue
@@ -201,7 +161,7 @@ pub fn sort_tiles(found: &mut Vec<Tile>, available: &mut Vec<Tile>, width: usize
             } else {
                 // must have top neighbour since it's not the first
                 if found.get(found.len() - width)?.aligns(&mut tile, &Border::BOTTOM) {
-                    found.push(tile);
+                    found.push(tile.clone());
                     match sort_tiles(found, available, width) {
                         None => {
                             // undo and continue
@@ -219,71 +179,54 @@ pub fn sort_tiles(found: &mut Vec<Tile>, available: &mut Vec<Tile>, width: usize
     None
 }
 
-pub fn get_product_of_corners(input: &str) -> Option<usize> {
-    let mut tiles = get_tiles(input)?;
-    let width = (tiles.len() as f64).sqrt() as usize;
-    let tiles = sort_tiles(&mut Vec::new(), &mut tiles, width)?;
-    Some((tiles.get(0)?.id as usize)
-        * (tiles.get(width - 1)?.id as usize)
-        * (tiles.last()?.id as usize)
-        * (tiles.get(tiles.len() - width)?.id as usize))
+pub fn get_product_of_corners(input: &str) -> Result<usize, AocError> {
+    let tiles = get_sorted_tiles(get_tiles(input)?)?;
+    let (right, bottom) = (tiles.width() - 1, tiles.height() - 1);
+    Ok([(0, 0), (right, 0), (0, bottom), (right, bottom)].iter()
+        .map(|&corner| tiles[corner].id as usize)
+        .product())
 }
 
-fn get_image(tiles: &[Tile]) -> Vec<bool> {
-    let width = (tiles.len() as f64).sqrt() as usize;
-    let mut image: Vec<bool> = vec![false; width * 8 * width * 8];
-
-    for (idx, tile) in tiles.iter().enumerate() {
-        let tile_row = idx / width;
-        let tile_col = idx % width;
-        for (bit_idx, &val) in tile.data.iter().enumerate().filter(|&(k, _)| {
-            k > 9 && k < 90 && k % 10 != 0 && k % 10 != 9
-        }) {
-            let row = bit_idx / 10;
-            let col = bit_idx % 10;
-            image[(tile_row * 8 + row - 1) * width * 8 + tile_col * 8 + col - 1] = val;
+/// Puts the tiles together without their borders
+fn get_image(tiles: &Grid<Tile>) -> Grid<bool> {
+    let mut image = Grid::filled(tiles.width() * 8, tiles.height() * 8, false);
+    for (tile_x, tile_y) in tiles.positions() {
+        let inner = tiles[(tile_x, tile_y)].data.crop((1, 1), 8, 8);
+        for (x, y) in inner.positions() {
+            image[(tile_x * 8 + x, tile_y * 8 + y)] = inner[(x, y)];
         }
     }
     image
 }
 
-pub fn get_water_roughness(tiles: Vec<Tile>) -> usize {
-    let width = (tiles.len() as f64).sqrt() as usize;
-    let sea_monster = get_sea_monster(width);
+pub fn get_water_roughness(tiles: Grid<Tile>) -> usize {
+    let sea_monster = get_sea_monster();
     let image = get_image(&tiles);
     let monsters = count_sea_monsters(tiles);
     image.iter().filter(|&v| *v).count() - monsters * sea_monster.len()
 }
 
-pub fn count_sea_monsters(tiles: Vec<Tile>) -> usize {
-    let width = (tiles.len() as f64).sqrt() as usize;
-    let sea_monster = get_sea_monster(width);
+pub fn count_sea_monsters(tiles: Grid<Tile>) -> usize {
+    let sea_monster = get_sea_monster();
     let mut image = get_image(&tiles);
     let mut max_count = 0;
     for _ in 0..4 {
         for _ in 0..2 {
-            for _ in 0..2 {
-                let mut count = 0;
-                for y in 0..(width * 8 - 3) {
-                    for x in 0..(width * 8 - 20) {
-                        if sea_monster.iter().all(|v| image[v + y * width * 8 + x]) {
-                            count += 1;
-                        }
-                    }
-                }
-                if count > max_count {
-                    max_count = count;
-                }
-                image = flip(&image, Border::LEFT);
+            let count = image.positions()
+                .filter(|&(x, y)| sea_monster.iter().all(|&(dx, dy)| image.get((x + dx, y + dy)) == Some(&true)))
+                .count();
+            if count > max_count {
+                max_count = count;
             }
-            image = flip(&image, Border::TOP);
+            image = image.flip_horizontal();
         }
-        image = rotate(&image);
+        image = image.rotate();
     }
     max_count
 }
 
-fn get_sea_monster(width: usize) -> Vec<usize> {
+/// Returns the positions of the sea monster's cells relative to its top left corner
+fn get_sea_monster() -> Vec<(usize, usize)> {
     let sea_monster = r"                  #
 #    ##    ##    ###
  #  #  #  #  #  #   ";
@@ -291,17 +234,42 @@ fn get_sea_monster(width: usize) -> Vec<usize> {
     for (lid, line) in sea_monster.lines().enumerate() {
         for (cid, chr) in line.chars().enumerate() {
             if chr == '#' {
-                monster.push(lid * width * 8 + cid);
+                monster.push((cid, lid));
             }
         }
     }
     monster
 }
 
+pub struct Day20 {
+    input: String,
+}
+
+impl Solver for Day20 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day20 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_product_of_corners(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        let tiles = get_tiles(&self.input)?;
+        Ok(get_water_roughness(get_sorted_tiles(tiles)?).into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day20::{Tile, get_tiles, sort_tiles, get_product_of_corners, count_sea_monsters, get_water_roughness, get_sorted_tiles};
     use crate::day20::Border;
+    use crate::grid::Grid;
+    use crate::error::AocError;
 
     #[test]
     fn test_get_tile() {
@@ -318,7 +286,7 @@ mod tests {
 ..###..###";
         let expected = Tile {
             id: 2311,
-            data: [false, false, true, true, false, true, false, false, true, false,
+            data: Grid::new(10, vec![false, false, true, true, false, true, false, false, true, false,
                 true, true, false, false, true, false, false, false, false, false,
                 true, false, false, false, true, true, false, false, true, false,
                 true, true, true, true, false, true, false, false, false, true,
@@ -327,9 +295,9 @@ mod tests {
                 false, true, false, true, false, true, false, false, true, true,
                 false, false, true, false, false, false, false, true, false, false,
                 true, true, true, false, false, false, true, false, true, false,
-                false, false, true, true, true, false, false, true, true, true],
+                false, false, true, true, true, false, false, true, true, true]),
         };
-        assert_eq!(Some(expected), Tile::new(input));
+        assert_eq!(Ok(expected), Tile::new(input));
     }
 
     #[test]
@@ -344,22 +312,22 @@ mod tests {
         }
         let mut rotated = Tile {
             id: 0,
-            data: input_data,
+            data: Grid::new(10, input_data.to_vec()),
         };
         rotated.rotate(90);
-        assert_eq!(Tile { id: 0, data: expected_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, expected_data.to_vec()) }, rotated);
         rotated.rotate(90);
-        assert_eq!(Tile { id: 0, data: input_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, rotated);
         rotated.rotate(90);
-        assert_eq!(Tile { id: 0, data: expected_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, expected_data.to_vec()) }, rotated);
         rotated.rotate(90);
-        assert_eq!(Tile { id: 0, data: input_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, rotated);
         rotated.rotate(90);
-        assert_ne!(Tile { id: 0, data: input_data }, rotated);
+        assert_ne!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, rotated);
         rotated.rotate(-90);
-        assert_eq!(Tile { id: 0, data: input_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, rotated);
         rotated.rotate(180);
-        assert_eq!(Tile { id: 0, data: input_data }, rotated);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, rotated);
     }
 
     #[test]
@@ -374,16 +342,16 @@ mod tests {
         }
         let mut flipped = Tile {
             id: 0,
-            data: input_data,
+            data: Grid::new(10, input_data.to_vec()),
         };
         flipped.flip(Border::LEFT);
-        assert_eq!(Tile { id: 0, data: expected_data }, flipped);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, expected_data.to_vec()) }, flipped);
         flipped.flip(Border::LEFT);
-        assert_eq!(Tile { id: 0, data: input_data }, flipped);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, flipped);
         flipped.flip(Border::TOP);
-        assert_eq!(Tile { id: 0, data: expected_data }, flipped);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, expected_data.to_vec()) }, flipped);
         flipped.flip(Border::TOP);
-        assert_eq!(Tile { id: 0, data: input_data }, flipped);
+        assert_eq!(Tile { id: 0, data: Grid::new(10, input_data.to_vec()) }, flipped);
     }
 
     #[test]
@@ -437,7 +405,7 @@ mod tests {
     fn test_get_seamonsters() {
         let input = std::fs::read_to_string("resources/day20_example.txt").unwrap();
         let tiles = sort_tiles(&mut Vec::new(), &mut get_tiles(&input).unwrap(), 3).unwrap();
-        assert_eq!(2, count_sea_monsters(tiles));
+        assert_eq!(2, count_sea_monsters(Grid::new(3, tiles)));
     }
 
     #[test]
@@ -454,4 +422,18 @@ mod tests {
         let tiles = get_sorted_tiles(get_tiles(&input).unwrap()).unwrap();
         println!("{:?}", get_water_roughness(tiles));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_tiles() {
+        let row = "..........\n";
+        let tile = format!("Tile 1:\n{}", row.repeat(10));
+        assert_eq!(Err(AocError::parse(20, 15, 4, "Unknown character 'o'")),
+                   get_tiles(&format!("{}\nTile 2:\n{}...o......\n{}", tile, row, row.repeat(8))));
+        assert_eq!(Err(AocError::parse(20, 1, 1, "Expected 'Tile <id>:', found 'Tile:'")),
+                   get_tiles(&format!("Tile:\n{}", row.repeat(10))));
+        assert_eq!(Err(AocError::parse(20, 11, 1, "Tiles have to be 10 by 10 cells")),
+                   get_tiles(&format!("Tile 1:\n{}", row.repeat(9))));
+        assert_eq!(Err(AocError::no_solution(20, "2 tiles can't be arranged in a square")),
+                   get_sorted_tiles(get_tiles(&format!("{}\n{}", tile, tile)).unwrap()));
+    }
+}
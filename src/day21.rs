@@ -6,19 +6,25 @@
 
  */
 use std::collections::{HashMap, HashSet};
+use crate::error::AocError;
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 21;
 
 /**
 Returns a map { Allergen => Ingredients }
 */
-pub fn get_allergen_map(input: &str) -> Option<HashMap<&str, HashSet<&str>>> {
+pub fn get_allergen_map(input: &str) -> Result<HashMap<&str, HashSet<&str>>, AocError> {
     let mut allergen_map = HashMap::new();
-    for line in input.lines() {
-        match line.trim().split_once(" (contains ") {
+    for line in lines(input) {
+        match line.split_once(" (contains ") {
             None => {
                 // no known allergens for this recipe
             }
             Some((ingredients, allergens)) => {
-                let allergens = allergens.trim_matches(')');
+                let allergens = allergens.strip_suffix(')')
+                    .ok_or_else(|| AocError::parse_at(DAY, input, allergens, "Expected the list of allergens to end with ')'"))?;
                 let ingredients: HashSet<&str> = ingredients.split_whitespace().collect();
                 for allergen in allergens.split(", ") {
                     match allergen_map.get_mut(allergen) {
@@ -29,13 +35,14 @@ pub fn get_allergen_map(input: &str) -> Option<HashMap<&str, HashSet<&str>>> {
             }
         };
     }
-    Some(allergen_map)
+    Ok(allergen_map)
 }
 
-fn remove_impossible_options(input: &str, allergen_map: &mut HashMap<&str, HashSet<&str>>) -> Option<()> {
+fn remove_impossible_options(input: &str, allergen_map: &mut HashMap<&str, HashSet<&str>>) -> Result<(), AocError> {
     while allergen_map.iter().any(|(_, ingredients)| ingredients.len() > 1) {
-        for recipe in input.lines() {
-            match recipe.trim().split_once(" (contains ") {
+        let options_before: usize = allergen_map.values().map(|ingredients| ingredients.len()).sum();
+        for recipe in lines(input) {
+            match recipe.split_once(" (contains ") {
                 None => {
                     // no known allergens for this recipe
                 }
@@ -43,15 +50,17 @@ fn remove_impossible_options(input: &str, allergen_map: &mut HashMap<&str, HashS
                     let allergens = allergens.trim_matches(')');
                     let ingredients: HashSet<&str> = ingredients.split_whitespace().collect();
                     for allergen in allergens.split(", ") {
-                        allergen_map.get_mut(allergen)?.retain(|&x| ingredients.contains(x));
+                        if let Some(options) = allergen_map.get_mut(allergen) {
+                            options.retain(|&x| ingredients.contains(x));
+                        }
                     }
                 }
             }
         }
-        let mut single_ingredients = HashSet::new();
+        let mut single_ingredients: HashSet<&str> = HashSet::new();
         for (_, single_ingredient) in allergen_map.iter() {
             if single_ingredient.len() == 1 {
-                single_ingredients.insert(*single_ingredient.iter().next()?);
+                single_ingredients.extend(single_ingredient.iter());
             }
         }
         for ingredient in single_ingredients {
@@ -61,16 +70,22 @@ fn remove_impossible_options(input: &str, allergen_map: &mut HashMap<&str, HashS
                 }
             }
         }
+        if let Some((allergen, _)) = allergen_map.iter().find(|(_, ingredients)| ingredients.is_empty()) {
+            return Err(AocError::no_solution(DAY, format!("No ingredient can contain {}", allergen)));
+        }
+        if allergen_map.values().map(|ingredients| ingredients.len()).sum::<usize>() == options_before {
+            return Err(AocError::no_solution(DAY, "Can't determine which ingredient contains which allergen"));
+        }
     }
-    Some(())
+    Ok(())
 }
 
 fn get_ingredients(input: &str) -> HashSet<&str> {
     let mut ingredients = HashSet::new();
-    for line in input.lines() {
-        match line.trim().split_once(" (contains ") {
+    for line in lines(input) {
+        match line.split_once(" (contains ") {
             None => {
-                ingredients.extend(line.trim().split_whitespace());
+                ingredients.extend(line.split_whitespace());
             }
             Some((ingredient_list, _)) => {
                 ingredients.extend(ingredient_list.trim().split_whitespace());
@@ -80,7 +95,7 @@ fn get_ingredients(input: &str) -> HashSet<&str> {
     ingredients
 }
 
-fn get_ingredients_without_allergens(input: &str) -> Option<HashSet<&str>> {
+fn get_ingredients_without_allergens(input: &str) -> Result<HashSet<&str>, AocError> {
     let mut allergen_map = get_allergen_map(input)?;
     remove_impossible_options(input, &mut allergen_map)?;
     let mut all_ingredients = get_ingredients(input);
@@ -89,19 +104,16 @@ fn get_ingredients_without_allergens(input: &str) -> Option<HashSet<&str>> {
             all_ingredients.remove(ingredient);
         }
     }
-    Some(all_ingredients)
+    Ok(all_ingredients)
 }
 
-pub fn count_ingredients_without_allergens(input: &str) -> usize {
-    let ingredients = match get_ingredients_without_allergens(input) {
-        None => { HashSet::new() }
-        Some(ingredients) => { ingredients }
-    };
+pub fn count_ingredients_without_allergens(input: &str) -> Result<usize, AocError> {
+    let ingredients = get_ingredients_without_allergens(input)?;
     let mut count = 0;
-    for line in input.lines() {
-        match line.trim().split_once(" (contains ") {
+    for line in lines(input) {
+        match line.split_once(" (contains ") {
             None => {
-                for recipe_ingredient in line.trim().split_whitespace() {
+                for recipe_ingredient in line.split_whitespace() {
                     if ingredients.contains(recipe_ingredient) {
                         count += 1;
                     }
@@ -116,19 +128,41 @@ pub fn count_ingredients_without_allergens(input: &str) -> usize {
             }
         };
     }
-    count
+    Ok(count)
 }
 
-pub fn get_canonical_dangerous_ingredients_list(input: &str) -> Option<String> {
+pub fn get_canonical_dangerous_ingredients_list(input: &str) -> Result<String, AocError> {
     let mut allergen_map = get_allergen_map(input)?;
-    remove_impossible_options(input, &mut allergen_map);
+    remove_impossible_options(input, &mut allergen_map)?;
     let mut keys: Vec<&str> = allergen_map.keys().copied().collect();
     keys.sort_unstable();
     let sorted_ingredients: Vec<&str> = keys
         .iter()
         .map(|&k| *allergen_map.get(k).unwrap().iter().next().unwrap())
         .collect();
-    Some(sorted_ingredients.join(","))
+    Ok(sorted_ingredients.join(","))
+}
+
+pub struct Day21 {
+    input: String,
+}
+
+impl Solver for Day21 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day21 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(count_ingredients_without_allergens(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_canonical_dangerous_ingredients_list(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
@@ -144,7 +178,7 @@ trh fvjkl sbzzf mxmxvkd (contains dairy)
 sqjhc fvjkl (contains soy)
 sqjhc mxmxvkd sbzzf (contains fish)
 ";
-        let allergen_map = get_allergen_map(input).ok_or("no allergen_map")?;
+        let allergen_map = get_allergen_map(input)?;
         assert!(allergen_map.get("fish").ok_or("no fish")?.contains("kfcds"));
         assert!(allergen_map.get("fish").ok_or("no fish")?.contains("sbzzf"));
         Ok(())
@@ -179,13 +213,13 @@ trh fvjkl sbzzf mxmxvkd (contains dairy)
 sqjhc fvjkl (contains soy)
 sqjhc mxmxvkd sbzzf (contains fish)
 ";
-        assert_eq!(5, count_ingredients_without_allergens(input));
+        assert_eq!(Ok(5), count_ingredients_without_allergens(input));
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day21.txt").unwrap();
-        println!("{}", count_ingredients_without_allergens(&input));
+        println!("{}", count_ingredients_without_allergens(&input).unwrap());
     }
 
     #[test]
@@ -203,4 +237,14 @@ sqjhc mxmxvkd sbzzf (contains fish)
         let input = std::fs::read_to_string("resources/day21.txt").unwrap();
         println!("{}", get_canonical_dangerous_ingredients_list(&input).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_recipes() {
+        assert_eq!(Err(AocError::parse(21, 2, 15, "Expected the list of allergens to end with ')'")),
+                   get_allergen_map("a b (contains x)\nc d (contains y"));
+        assert_eq!(Err(AocError::no_solution(21, "No ingredient can contain x")),
+                   count_ingredients_without_allergens("a b (contains x)\nc d (contains x)"));
+        assert_eq!(Err(AocError::no_solution(21, "Can't determine which ingredient contains which allergen")),
+                   get_canonical_dangerous_ingredients_list("a b (contains x)"));
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::{VecDeque, HashSet};
+use crate::error::{parse_fragment, AocError};
+use crate::input::{blocks, lines};
+use crate::solver::{Answer, Solver};
 
-pub fn play(input: &str) -> usize {
-    let mut decks: Vec<VecDeque<u8>> = get_decks(input);
+const DAY: u8 = 22;
+
+pub fn play(input: &str) -> Result<usize, AocError> {
+    let mut decks: Vec<VecDeque<u8>> = get_decks(input)?;
     while decks.iter().all(|d| !d.is_empty()) {
         //println!("{:?}", decks);
         let mut round: Vec<u8> = decks
@@ -24,17 +29,17 @@ pub fn play(input: &str) -> usize {
     //println!("{:?}", decks);
     for deck in decks {
         if !deck.is_empty() {
-            return get_winning_score(&deck);
+            return Ok(get_winning_score(&deck));
         }
     }
     panic!("No deck with any cards");
 }
 
-pub fn start_play_recursive(input: &str) -> usize {
-    let mut decks = get_decks(input);
+pub fn start_play_recursive(input: &str) -> Result<usize, AocError> {
+    let mut decks = get_decks(input)?;
     let winner = play_recursive(&mut decks);
     //println!("final decks: {:?}", decks);
-    get_winning_score(&decks.get(winner).unwrap())
+    Ok(get_winning_score(&decks[winner]))
 }
 
 fn play_recursive(decks: &mut Vec<VecDeque<u8>>) -> usize {
@@ -91,18 +96,60 @@ fn get_winning_score(deck: &VecDeque<u8>) -> usize {
     });
 }
 
-fn get_decks(input: &str) -> Vec<VecDeque<u8>> {
+fn get_decks(input: &str) -> Result<Vec<VecDeque<u8>>, AocError> {
     let mut decks = Vec::new();
-    for player in input.trim().split("\n\n") {
-        decks.push(player.lines().skip(1).map(|card| card.parse().unwrap()).collect())
+    for player in blocks(input) {
+        let mut cards = lines(player);
+        let header = cards.next().unwrap_or_default();
+        if !header.starts_with("Player ") || !header.ends_with(':') {
+            return Err(AocError::parse_at(DAY, input, header, format!("Expected 'Player <number>:', found '{}'", header)));
+        }
+        let mut deck = VecDeque::new();
+        for card in cards {
+            let value: u8 = parse_fragment(DAY, input, card)?;
+            if value == 0 {
+                return Err(AocError::parse_at(DAY, input, card, "Cards have to be positive"));
+            }
+            deck.push_back(value);
+        }
+        if deck.is_empty() {
+            return Err(AocError::parse_at(DAY, input, header, "The player has no cards"));
+        }
+        decks.push(deck);
+    }
+    if decks.len() != 2 {
+        return Err(AocError::no_solution(DAY, format!("The game needs 2 players, found {}", decks.len())));
+    }
+    Ok(decks)
+}
+
+pub struct Day22 {
+    input: String,
+}
+
+impl Solver for Day22 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day22 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(play(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(start_play_recursive(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
     }
-    decks
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day22::{get_decks, play, start_play_recursive};
     use std::collections::VecDeque;
+    use crate::error::AocError;
 
     fn get_example_input() -> String {
         String::from(r"Player 1:
@@ -126,25 +173,25 @@ Player 2:
         let input = get_example_input();
         let expected = vec![VecDeque::from(vec![9, 2, 6, 3, 1]),
                             VecDeque::from(vec![5, 8, 4, 7, 10])];
-        assert_eq!(expected, get_decks(&input));
+        assert_eq!(Ok(expected), get_decks(&input));
     }
 
     #[test]
     fn test_play() {
         let input = get_example_input();
-        assert_eq!(306, play(&input));
+        assert_eq!(Ok(306), play(&input));
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day22.txt").unwrap();
-        println!("{}", play(&input));
+        println!("{}", play(&input).unwrap());
     }
 
     #[test]
     fn test_recursive_game() {
         let input = get_example_input();
-        assert_eq!(291, start_play_recursive(&input));
+        assert_eq!(Ok(291), start_play_recursive(&input));
     }
 
     #[test]
@@ -157,13 +204,21 @@ Player 2:
 2
 29
 14";
-        println!("{}", start_play_recursive(input));
+        println!("{}", start_play_recursive(input).unwrap());
     }
 
     #[test]
     #[ignore]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day22.txt").unwrap();
-        println!("{}", start_play_recursive(&input));
+        println!("{}", start_play_recursive(&input).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_decks() {
+        assert_eq!(Err(AocError::parse(22, 3, 1, "Invalid value 'x': invalid digit found in string")),
+                   get_decks("Player 1:\n9\nx\n\nPlayer 2:\n5"));
+        assert_eq!(Err(AocError::parse(22, 4, 1, "The player has no cards")), get_decks("Player 1:\n9\n\nPlayer 2:"));
+        assert_eq!(Err(AocError::no_solution(22, "The game needs 2 players, found 1")), get_decks("Player 1:\n9"));
+    }
+}
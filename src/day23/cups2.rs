@@ -1,3 +1,7 @@
+use crate::error::AocError;
+
+const DAY: u8 = 23;
+
 #[derive(Debug)]
 pub struct Cups {
     cups: Vec<u32>,
@@ -5,17 +9,23 @@ pub struct Cups {
 }
 
 impl Cups {
-    pub fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Result<Self, AocError> {
+        validate_labels(input)?;
         let cups: Vec<u32> = vec![0u32; input.trim().len() + 1];
         let mut cup = Cups {
             cups,
             current_cup: 0,
         };
         cup.init_with_values(input);
-        cup
+        Ok(cup)
     }
 
-    pub fn new_with_length(input: &str, length: usize) -> Self {
+    pub fn new_with_length(input: &str, length: usize) -> Result<Self, AocError> {
+        validate_labels(input)?;
+        let input = input.trim();
+        if length < input.len() {
+            return Err(AocError::no_solution(DAY, format!("Can't fit {} labels into {} cups", input.len(), length)));
+        }
         let cups: Vec<u32> = vec![0u32; length + 1];
         let mut cup = Cups {
             cups,
@@ -28,7 +38,7 @@ impl Cups {
             cup.cups[i] = (i + 1) as u32;
         }
         cup.cups[length] = cup.current_cup;
-        cup
+        Ok(cup)
     }
 
     fn init_with_values(&mut self, input: &str) {
@@ -88,9 +98,9 @@ impl Cups {
 
     #[allow(dead_code)]
     pub fn get_order(&self) -> String {
-        let mut res = Vec::with_capacity(8);
+        let mut res = Vec::with_capacity(self.cups.len() - 2);
         let mut cup = self.cups[1];
-        for _ in 0..8 {
+        for _ in 0..self.cups.len() - 2 {
             res.push(cup);
             cup = self.cups[cup as usize];
         }
@@ -105,20 +115,42 @@ impl Cups {
     }
 }
 
+/// The labels have to be the digits from 1 up to the number of cups, each used once
+fn validate_labels(input: &str) -> Result<(), AocError> {
+    let input = input.trim();
+    if input.len() < 5 {
+        return Err(AocError::no_solution(DAY, "At least 5 cups are needed to play"));
+    }
+    let mut seen = [false; 10];
+    for (idx, chr) in input.chars().enumerate() {
+        match chr.to_digit(10) {
+            Some(value) if value >= 1 && value as usize <= input.len() && !seen[value as usize] => {
+                seen[value as usize] = true;
+            }
+            _ => {
+                return Err(AocError::parse(DAY, 1, idx + 1,
+                                           format!("Expected each label from 1 to {} exactly once, found '{}'", input.len(), chr)));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_get_cups() {
-        let cups = Cups::new("389125467");
+        let cups = Cups::new("389125467").unwrap();
         assert_eq!(8, cups.cups[3]);
         assert_eq!(3, cups.cups[7]);
     }
 
     #[test]
     fn test_get_extracted_cups() {
-        let mut cups = Cups::new("389125467");
+        let mut cups = Cups::new("389125467").unwrap();
         assert_eq!(vec![8, 9, 1], cups.extract_n_elements_after_current(3));
         assert_eq!(2, cups.cups[cups.current_cup as usize]);
     }
@@ -126,14 +158,14 @@ mod tests {
 
     #[test]
     fn test_do_move() {
-        let mut cups = Cups::new("389125467");
+        let mut cups = Cups::new("389125467").unwrap();
         cups.do_move();
         assert_eq!(String::from("54673289"), cups.get_order());
     }
 
     #[test]
     fn test_get_destination_cup() {
-        let mut cups = Cups::new("389125467");
+        let mut cups = Cups::new("389125467").unwrap();
         assert_eq!(9, cups.get_destination_cup(&vec![2, 1]));
         let extracted = cups.extract_n_elements_after_current(3);
         assert_eq!(2, cups.get_destination_cup(&extracted));
@@ -141,7 +173,7 @@ mod tests {
 
     #[test]
     fn test_part1_examples() {
-        let mut cups = Cups::new("389125467");
+        let mut cups = Cups::new("389125467").unwrap();
         println!("{:?}", cups.cups);
         cups.do_moves(10);
         assert_eq!("92658374", &cups.get_order());
@@ -151,14 +183,14 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let mut cups = Cups::new("467528193");
+        let mut cups = Cups::new("467528193").unwrap();
         cups.do_moves(100);
         assert_eq!("43769582", cups.get_order());
     }
 
     #[test]
     fn test_big_cups() {
-        let cups = Cups::new_with_length("389125467", 1_000_000);
+        let cups = Cups::new_with_length("389125467", 1_000_000).unwrap();
         let first_20 = &cups.cups[0..20];
         assert_eq!(vec![0, 2, 5, 8, 6, 4, 7, 10, 9, 1, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20], first_20);
         assert_eq!(3, cups.cups[1_000_000]);
@@ -166,10 +198,20 @@ mod tests {
 
     #[test]
     fn test_part2_example() {
-        let mut cups = Cups::new_with_length("389125467", 1_000_000);
+        let mut cups = Cups::new_with_length("389125467", 1_000_000).unwrap();
         cups.do_moves(10_000_000);
         let first_20 = &cups.cups[0..20];
         println!("{:?}", first_20);
         assert_eq!((934001, 159792), cups.get_cups_after_one());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_labels() {
+        assert_eq!(Err(AocError::parse(23, 1, 4, "Expected each label from 1 to 9 exactly once, found '8'")),
+                   Cups::new("389825467").map(|_| ()));
+        assert_eq!(Err(AocError::parse(23, 1, 5, "Expected each label from 1 to 6 exactly once, found '7'")),
+                   Cups::new("126375").map(|_| ()));
+        assert!(Cups::new("1234").is_err());
+        assert!(Cups::new_with_length("389125467", 8).is_err());
+    }
+}
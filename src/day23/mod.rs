@@ -1,4 +1,37 @@
 pub mod cups2;
 
 #[allow(dead_code)]
-mod cups;
\ No newline at end of file
+mod cups;
+
+use crate::error::AocError;
+use crate::solver::{Answer, Solver};
+use cups2::Cups;
+
+const DAY: u8 = 23;
+
+pub struct Day23 {
+    labels: String,
+}
+
+impl Solver for Day23 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day23 { labels: input.trim().to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        let mut cups = Cups::new(&self.labels)?;
+        cups.do_moves(100);
+        Ok(cups.get_order().into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        let mut cups = Cups::new_with_length(&self.labels, 1_000_000)?;
+        cups.do_moves(10_000_000);
+        let (first, second) = cups.get_cups_after_one();
+        Ok((first as u64 * second as u64).into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
@@ -1,5 +1,10 @@
 use std::collections::HashSet;
 use std::ops::{Range, Add};
+use crate::error::AocError;
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 24;
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 pub struct Coordinate(isize, isize, isize);
@@ -28,16 +33,15 @@ impl<'a, 'b> Add<&'b Coordinate> for &'a Coordinate {
     }
 }
 
-fn get_coordinates(input: &str) -> Coordinate {
+fn get_coordinates(input: &str) -> Result<Coordinate, AocError> {
 
     // https://www.redblobgames.com/grids/hexagons/
-    let mut chars = input.trim().chars();
+    let mut chars = input.trim().chars().enumerate();
     let mut position = Coordinate(0, 0, 0);
-    while let Some(char) = chars.next() {
+    while let Some((idx, char)) = chars.next() {
         match char {
             'n' => {
-                let second_char = chars.next().unwrap();
-                match second_char {
+                match get_second_char(&mut chars, idx)? {
                     'e' => {
                         position.0 += 1;
                         position.2 -= 1;
@@ -46,12 +50,11 @@ fn get_coordinates(input: &str) -> Coordinate {
                         position.1 += 1;
                         position.2 -= 1;
                     }
-                    _ => panic!()
+                    _ => unreachable!()
                 }
             }
             's' => {
-                let second_char = chars.next().unwrap();
-                match second_char {
+                match get_second_char(&mut chars, idx)? {
                     'e' => {
                         position.1 -= 1;
                         position.2 += 1;
@@ -60,7 +63,7 @@ fn get_coordinates(input: &str) -> Coordinate {
                         position.0 -= 1;
                         position.2 += 1;
                     }
-                    _ => panic!()
+                    _ => unreachable!()
                 }
             }
             'e' => {
@@ -71,28 +74,35 @@ fn get_coordinates(input: &str) -> Coordinate {
                 position.0 -= 1;
                 position.1 += 1;
             }
-            _ => panic!()
+            _ => return Err(AocError::parse(DAY, 1, idx + 1, format!("Unknown direction '{}'", char)))
         }
     }
-    position
+    Ok(position)
 }
 
-pub fn flip_tiles(input: &str) -> HashSet<Coordinate> {
+/// Returns the second character of a diagonal direction, following the 'n' or 's' at column `idx + 1`
+fn get_second_char(chars: &mut impl Iterator<Item=(usize, char)>, idx: usize) -> Result<char, AocError> {
+    match chars.next() {
+        Some((_, chr)) if chr == 'e' || chr == 'w' => Ok(chr),
+        Some((second_idx, chr)) => Err(AocError::parse(DAY, 1, second_idx + 1, format!("Expected 'e' or 'w', found '{}'", chr))),
+        None => Err(AocError::parse(DAY, 1, idx + 2, "Expected 'e' or 'w', found end of line")),
+    }
+}
+
+pub fn flip_tiles(input: &str) -> Result<HashSet<Coordinate>, AocError> {
     let mut black_tiles = HashSet::new();
-    for line in input.trim().lines() {
-        let coords = get_coordinates(line);
+    for line in lines(input) {
+        let coords = get_coordinates(line).map_err(|e| e.relative_to(input, line))?;
         if !black_tiles.remove(&coords) {
             black_tiles.insert(coords);
         }
     }
-    black_tiles
+    Ok(black_tiles)
 }
 
 fn tick(black_tiles: HashSet<Coordinate>, survive: &Range<usize>, revive: &Range<usize>) -> HashSet<Coordinate> {
-    let offsets: Vec<Coordinate> = "ne,e,se,sw,w,nw"
-        .split(',')
-        .map(|offset| get_coordinates(offset))
-        .collect();
+    let offsets = [Coordinate(1, 0, -1), Coordinate(1, -1, 0), Coordinate(0, -1, 1),
+                   Coordinate(-1, 0, 1), Coordinate(-1, 1, 0), Coordinate(0, 1, -1)];
     let mut new_grid: HashSet<Coordinate> = HashSet::new();
     let mut neighbours: HashSet<Coordinate> = HashSet::new();
     for black_tile in black_tiles.iter() {
@@ -128,40 +138,62 @@ pub fn tick_n_times(mut black_tiles: HashSet<Coordinate>, num: usize, survive: &
     black_tiles
 }
 
+pub struct Day24 {
+    black_tiles: HashSet<Coordinate>,
+}
+
+impl Solver for Day24 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day24 { black_tiles: flip_tiles(input)? })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(self.black_tiles.len().into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(tick_n_times(self.black_tiles.clone(), 100, &(1..3), &(2..3)).len().into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::AocError;
 
     #[test]
     fn test_hex_path() {
-        assert_eq!(Coordinate(1, -1, 0), get_coordinates("e"));
-        assert_eq!(Coordinate(0, -1, 1), get_coordinates("se"));
-        assert_eq!(Coordinate(-1, 0, 1), get_coordinates("sw"));
-        assert_eq!(Coordinate(-1, 1, 0), get_coordinates("w"));
-        assert_eq!(Coordinate(0, 1, -1), get_coordinates("nw"));
-        assert_eq!(Coordinate(1, 0, -1), get_coordinates("ne"));
-        assert_eq!(Coordinate(0, 0, 0), get_coordinates("nwwswee"));
+        assert_eq!(Coordinate(1, -1, 0), get_coordinates("e").unwrap());
+        assert_eq!(Coordinate(0, -1, 1), get_coordinates("se").unwrap());
+        assert_eq!(Coordinate(-1, 0, 1), get_coordinates("sw").unwrap());
+        assert_eq!(Coordinate(-1, 1, 0), get_coordinates("w").unwrap());
+        assert_eq!(Coordinate(0, 1, -1), get_coordinates("nw").unwrap());
+        assert_eq!(Coordinate(1, 0, -1), get_coordinates("ne").unwrap());
+        assert_eq!(Coordinate(0, 0, 0), get_coordinates("nwwswee").unwrap());
     }
 
     #[test]
     fn test_example1() {
         let input = std::fs::read_to_string("resources/day24_example.txt").unwrap();
-        let black_tiles = flip_tiles(&input);
+        let black_tiles = flip_tiles(&input).unwrap();
         assert_eq!(10, black_tiles.len());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day24.txt").unwrap();
-        let black_tiles = flip_tiles(&input);
+        let black_tiles = flip_tiles(&input).unwrap();
         println!("{}", black_tiles.len());
     }
 
     #[test]
     fn test_example2() {
         let input = std::fs::read_to_string("resources/day24_example.txt").unwrap();
-        let black_tiles = flip_tiles(&input);
+        let black_tiles = flip_tiles(&input).unwrap();
         let black_tiles = tick_n_times(black_tiles, 100, &(1..3), &(2..3));
         assert_eq!(2208, black_tiles.len());
     }
@@ -169,8 +201,15 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day24.txt").unwrap();
-        let black_tiles = flip_tiles(&input);
+        let black_tiles = flip_tiles(&input).unwrap();
         let black_tiles = tick_n_times(black_tiles, 100, &(1..3), &(2..3));
         println!("{}", black_tiles.len());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_paths() {
+        assert_eq!(Err(AocError::parse(24, 2, 3, "Expected 'e' or 'w', found 'n'")), flip_tiles("e\nwsnw"));
+        assert_eq!(Err(AocError::parse(24, 1, 3, "Expected 'e' or 'w', found end of line")), flip_tiles("en"));
+        assert_eq!(Err(AocError::parse(24, 1, 2, "Unknown direction 'x'")), flip_tiles("ex"));
+    }
+}
@@ -1,3 +1,9 @@
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 25;
+
 pub const MODULO: u128 = 20201227;
 pub const SUBJECT_NUMBER: u128 = 7;
 
@@ -9,7 +15,7 @@ pub fn transform(subject_number: u128, loop_size: u128) -> u128 {
     value
 }
 
-pub fn crack_transform(subject_number: u128, public_key: u128) -> u128 {
+pub fn crack_transform(subject_number: u128, public_key: u128) -> Result<u128, AocError> {
     let mut loop_size = 0;
     let mut value = 1;
     loop {
@@ -18,15 +24,52 @@ pub fn crack_transform(subject_number: u128, public_key: u128) -> u128 {
         if value == public_key {
             break;
         }
+        if loop_size == MODULO {
+            return Err(AocError::no_solution(DAY, format!("No loop size transforms {} into {}", subject_number, public_key)));
+        }
     }
-    loop_size
+    Ok(loop_size)
 }
 
+pub struct Day25 {
+    card_public_key: u128,
+    door_public_key: u128,
+}
+
+impl Solver for Day25 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let mut keys = lines(input);
+        match (keys.next(), keys.next()) {
+            (Some(card_public_key), Some(door_public_key)) => Ok(Day25 {
+                card_public_key: parse_fragment(DAY, input, card_public_key)?,
+                door_public_key: parse_fragment(DAY, input, door_public_key)?,
+            }),
+            _ => Err(AocError::parse(DAY, 1, 1, "Expected two public keys")),
+        }
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        let door_loop_size = crack_transform(SUBJECT_NUMBER, self.door_public_key)?;
+        Ok(transform(self.card_public_key, door_loop_size).into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Err(AocError::UnknownPart { day: DAY, part: 2 })
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parts() -> &'static [u8] {
+        &[1]
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
+    use crate::error::AocError;
 
     #[test]
     fn test_transform() {
@@ -36,8 +79,9 @@ mod tests {
 
     #[test]
     fn test_crack() {
-        assert_eq!(8, crack_transform(SUBJECT_NUMBER, 5764801));
-        assert_eq!(11, crack_transform(SUBJECT_NUMBER, 17807724));
+        assert_eq!(Ok(8), crack_transform(SUBJECT_NUMBER, 5764801));
+        assert_eq!(Ok(11), crack_transform(SUBJECT_NUMBER, 17807724));
+        assert!(crack_transform(SUBJECT_NUMBER, MODULO).is_err());
     }
 
     #[test]
@@ -49,9 +93,16 @@ mod tests {
     #[test]
     fn test_part1() {
         let public_keys: (u128, u128) = (18499292, 8790390);
-        let loop_sizes = (crack_transform(SUBJECT_NUMBER, public_keys.0),
-                          crack_transform(SUBJECT_NUMBER, public_keys.1));
+        let loop_sizes = (crack_transform(SUBJECT_NUMBER, public_keys.0).unwrap(),
+                          crack_transform(SUBJECT_NUMBER, public_keys.1).unwrap());
         println!("{:?}", loop_sizes);
         println!("{}", transform(public_keys.0, loop_sizes.1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_keys() {
+        assert_eq!(Err(AocError::parse(25, 1, 1, "Expected two public keys")), Day25::parse("5764801").map(|_| ()));
+        assert_eq!(Err(AocError::parse(25, 2, 1, "Invalid value 'x': invalid digit found in string")),
+                   Day25::parse("5764801\nx").map(|_| ()));
+    }
+}
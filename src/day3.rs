@@ -1,20 +1,67 @@
-use std::error::Error;
-
-pub fn slide_down(map: &str, right: usize, down: usize) -> Result<usize, Box<dyn Error>> {
-    let map = map.trim();
-    let mut position_x = 0;
-    let mut num_trees: usize = 0;
-    let map_width = map.find('\n').ok_or("No line breaks in map")?;
-    for row in map.split('\n').step_by(down) {
-        if row.chars().nth(position_x).ok_or_else(|| format!("illegal index: {}", position_x))? == '#' {
-            num_trees += 1;
-        }
-        position_x = (position_x + right) % map_width;
+use std::ops::RangeInclusive;
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 3;
+
+/// Number of trees hit on a slope, moving `right` and `down` in each step
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SlopeRating {
+    pub right: usize,
+    pub down: usize,
+    pub trees: usize,
+}
+
+/// Parses the map of open squares (`.`) and trees (`#`), where `true` marks a tree. All rows need the same length
+pub fn parse_map(map: &str) -> Result<Grid<bool>, AocError> {
+    Grid::parse(DAY, map, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+/// Returns the positions the toboggan passes from the top left to the bottom of the map. The map repeats to the right
+fn route(map: &Grid<bool>, right: usize, down: usize) -> Result<impl Iterator<Item=(usize, usize)> + '_, AocError> {
+    if down == 0 {
+        return Err(AocError::no_solution(DAY, "The toboggan has to move down"));
     }
+    Ok((0..map.height()).step_by(down)
+        .enumerate()
+        .map(move |(step, y)| (step * right % map.width(), y)))
+}
+
+pub fn slide_down(map: &Grid<bool>, right: usize, down: usize) -> Result<usize, AocError> {
+    let num_trees = route(map, right, down)?.filter(|&position| map[position]).count();
     Ok(num_trees)
 }
 
-pub fn multiply_slopes(map: &str) -> Result<usize, Box<dyn Error>> {
+/// Evaluates every combination of the given steps to the right and down, and returns the slopes ordered by the number
+/// of trees, the safest first. Slopes with the same number of trees are ordered by `down`, then by `right`
+pub fn rank_slopes(map: &Grid<bool>, rights: RangeInclusive<usize>, downs: RangeInclusive<usize>)
+    -> Result<Vec<SlopeRating>, AocError> {
+    let mut ratings = Vec::new();
+    for down in downs {
+        for right in rights.clone() {
+            ratings.push(SlopeRating { right, down, trees: slide_down(map, right, down)? });
+        }
+    }
+    ratings.sort_by_key(|rating| (rating.trees, rating.down, rating.right));
+    Ok(ratings)
+}
+
+/// Draws the route of a slope on the map, marking the passed open squares with `O` and the hit trees with `X`
+pub fn render_route(map: &Grid<bool>, right: usize, down: usize) -> Result<String, AocError> {
+    let mut rendered = map.map(|&tree| if tree { '#' } else { '.' });
+    for position in route(map, right, down)? {
+        rendered[position] = if map[position] { 'X' } else { 'O' };
+    }
+    Ok(rendered.to_string())
+}
+
+pub fn multiply_slopes(map: &Grid<bool>) -> Result<usize, AocError> {
     let mut product = 1;
     product *= slide_down(map, 1, 1)?;
     product *= slide_down(map, 3, 1)?;
@@ -24,6 +71,28 @@ pub fn multiply_slopes(map: &str) -> Result<usize, Box<dyn Error>> {
     Ok(product)
 }
 
+pub struct Day3 {
+    map: Grid<bool>,
+}
+
+impl Solver for Day3 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day3 { map: parse_map(input)? })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(slide_down(&self.map, 3, 1)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(multiply_slopes(&self.map)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,14 +111,14 @@ mod tests {
 #...##....#
 .#..#...#.#
 ";
-        assert_eq!(7, slide_down(map, 3, 1).unwrap());
+        assert_eq!(7, slide_down(&parse_map(map).unwrap(), 3, 1).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day3.txt")
             .expect("Error reading file to string");
-        println!("{}", slide_down(input.as_str(), 3, 1).expect("Error executing slide_down"));
+        println!("{}", slide_down(&parse_map(&input).unwrap(), 3, 1).expect("Error executing slide_down"));
     }
 
     #[test]
@@ -66,6 +135,7 @@ mod tests {
 #...##....#
 .#..#...#.#
 ";
+        let map = &parse_map(map).unwrap();
         assert_eq!(2, slide_down(map, 1, 1).unwrap());
         assert_eq!(7, slide_down(map, 3, 1).unwrap());
         assert_eq!(3, slide_down(map, 5, 1).unwrap());
@@ -78,6 +148,33 @@ mod tests {
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day3.txt")
             .expect("Error reading file to string");
-        println!("{}", multiply_slopes(input.as_str()).unwrap());
+        println!("{}", multiply_slopes(&parse_map(&input).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_rank_slopes() {
+        let map = &parse_map("..#\n#..\n.#.\n..#").unwrap();
+        let ratings = rank_slopes(map, 0..=2, 1..=2).unwrap();
+        assert_eq!(6, ratings.len());
+        assert_eq!(SlopeRating { right: 1, down: 1, trees: 0 }, ratings[0]);
+        assert_eq!(SlopeRating { right: 0, down: 2, trees: 0 }, ratings[1]);
+        assert_eq!(SlopeRating { right: 1, down: 2, trees: 1 }, ratings[5]);
+        assert_eq!(vec![0, 0, 0, 1, 1, 1], ratings.iter().map(|rating| rating.trees).collect::<Vec<_>>());
+        assert!(rank_slopes(map, 1..=3, 0..=1).is_err());
+    }
+
+    #[test]
+    fn test_render_route() {
+        let map = &parse_map("..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#").unwrap();
+        assert_eq!("O.##.......\n#..O#...#..\n.#....X..#.\n..#.#...#O#", render_route(map, 3, 1).unwrap());
+        assert_eq!("O.#\n#..\n.#O", render_route(&parse_map("..#\n#..\n.#.").unwrap(), 2, 2).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(Err(AocError::parse(3, 2, 1, "Expected 3 columns, found 2")), parse_map("...\n..\n..."));
+        assert_eq!(Err(AocError::parse(3, 3, 3, "Unknown character 'O'")), parse_map("...\n#..\n..O"));
+        assert_eq!(Err(AocError::parse(3, 1, 1, "The map is empty")), parse_map(""));
+        assert!(slide_down(&parse_map("...").unwrap(), 1, 0).is_err());
     }
 }
@@ -0,0 +1,132 @@
+use crate::day4::get_field;
+use crate::day4::schema::Schema;
+use crate::error::{locate, AocError};
+use crate::input::blocks;
+
+/// Field whose value violates its rule
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidField {
+    pub name: String,
+    pub value: String,
+    pub reason: String,
+}
+
+/// Everything that is wrong with a passport. `line` is the 1-based line of the batch file on which the passport starts
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PassportDiagnostics {
+    pub line: usize,
+    pub fields: Vec<(String, String)>,
+    pub missing: Vec<String>,
+    pub invalid: Vec<InvalidField>,
+    pub duplicates: Vec<String>,
+    pub unknown: Vec<String>,
+    /// Parse errors of the fields that are not of the form `name:value`, located within the batch file
+    pub malformed: Vec<AocError>,
+}
+
+impl PassportDiagnostics {
+    /// Checks the passport in the same way as `Schema::is_valid`, which also rejects malformed fields. Duplicate and
+    /// unknown fields are only reported, they don't make a passport invalid
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.invalid.is_empty() && self.malformed.is_empty()
+    }
+}
+
+/// Checks every passport of the batch against the schema and lists its problems. Malformed fields are reported on
+/// their passport, the well-formed fields of that passport are still checked
+pub fn diagnose_passports(batch_file: &str, schema: &Schema) -> Vec<PassportDiagnostics> {
+    blocks(batch_file)
+        .map(|passport| {
+            let mut fields = Vec::new();
+            let mut malformed = Vec::new();
+            for field in passport.split_ascii_whitespace() {
+                match get_field(batch_file, field) {
+                    Ok(field) => fields.push(field),
+                    Err(e) => malformed.push(e),
+                }
+            }
+            diagnose(&fields, malformed, schema, locate(batch_file, passport).0)
+        })
+        .collect()
+}
+
+fn diagnose(fields: &[(&str, &str)], malformed: Vec<AocError>, schema: &Schema, line: usize) -> PassportDiagnostics {
+    let missing = schema.fields().iter()
+        .filter(|spec| spec.required && fields.iter().all(|&(name, _)| name != spec.name))
+        .map(|spec| spec.name.clone())
+        .collect();
+    let mut invalid = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut unknown = Vec::new();
+    for (idx, &(name, value)) in fields.iter().enumerate() {
+        let is_first = fields[..idx].iter().all(|&(other, _)| other != name);
+        if !is_first {
+            if !duplicates.iter().any(|duplicate| duplicate == name) {
+                duplicates.push(name.to_owned());
+            }
+        } else if schema.get(name).is_none() {
+            unknown.push(name.to_owned());
+        }
+        if let Some(reason) = schema.get(name).and_then(|spec| spec.rule.violation(value)) {
+            invalid.push(InvalidField { name: name.to_owned(), value: value.to_owned(), reason });
+        }
+    }
+    PassportDiagnostics {
+        line,
+        fields: fields.iter().map(|&(name, value)| (name.to_owned(), value.to_owned())).collect(),
+        missing,
+        invalid,
+        duplicates,
+        unknown,
+        malformed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_passports() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\n\
+                     iyr:2013 ecl:zzz cid:350 eyr:2023 pid:028048884\nhcl:#cfa07d byr:1929 ecl:amb foo:1 byr:2030";
+        let diagnostics = diagnose_passports(input, &Schema::north_pole());
+        assert_eq!(2, diagnostics.len());
+        assert!(diagnostics[0].is_valid());
+        assert_eq!(1, diagnostics[0].line);
+        assert_eq!(8, diagnostics[0].fields.len());
+        assert_eq!((String::from("ecl"), String::from("gry")), diagnostics[0].fields[0]);
+        assert!(diagnostics[0].missing.is_empty() && diagnostics[0].duplicates.is_empty() && diagnostics[0].unknown.is_empty());
+        assert!(diagnostics[0].malformed.is_empty());
+
+        let diagnostics = &diagnostics[1];
+        assert!(!diagnostics.is_valid());
+        assert_eq!(4, diagnostics.line);
+        assert_eq!(vec![String::from("hgt")], diagnostics.missing);
+        assert_eq!(vec![
+            InvalidField { name: String::from("ecl"), value: String::from("zzz"),
+                reason: String::from("'zzz' is not one of amb, blu, brn, gry, grn, hzl, oth") },
+            InvalidField { name: String::from("byr"), value: String::from("2030"),
+                reason: String::from("2030 is not between 1920 and 2002") },
+        ], diagnostics.invalid);
+        assert_eq!(vec![String::from("ecl"), String::from("byr")], diagnostics.duplicates);
+        assert_eq!(vec![String::from("foo")], diagnostics.unknown);
+    }
+
+    #[test]
+    fn test_malformed_passport() {
+        let diagnostics = diagnose_passports("ecl:gry\n\nhcl:#ae17e1 pid iyr2013\nbyr:1900", &Schema::north_pole());
+        assert_eq!(2, diagnostics.len());
+        assert!(diagnostics[0].malformed.is_empty());
+        assert_eq!(vec![AocError::parse(4, 3, 13, "Expected 'name:value', found 'pid'"),
+                        AocError::parse(4, 3, 17, "Expected 'name:value', found 'iyr2013'")], diagnostics[1].malformed);
+        assert_eq!(vec![(String::from("hcl"), String::from("#ae17e1")), (String::from("byr"), String::from("1900"))],
+                   diagnostics[1].fields);
+        assert_eq!(vec![String::from("byr")], diagnostics[1].invalid.iter().map(|field| field.name.clone()).collect::<Vec<_>>());
+        assert!(!diagnostics[1].is_valid());
+        let only_malformed = diagnose_passports("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:183cm x",
+                                                &Schema::north_pole());
+        assert!(only_malformed[0].missing.is_empty() && only_malformed[0].invalid.is_empty());
+        assert!(!only_malformed[0].is_valid());
+    }
+}
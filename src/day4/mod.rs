@@ -0,0 +1,167 @@
+pub mod diagnostics;
+pub mod records;
+pub mod schema;
+
+use crate::error::AocError;
+use crate::input::blocks;
+use crate::solver::{Answer, Solver};
+use schema::Schema;
+
+const DAY: u8 = 4;
+
+lazy_static! {
+    static ref NORTH_POLE: Schema = Schema::north_pole();
+}
+
+/// Splits a passport into its `name:value` fields
+fn get_fields(input: &str) -> Result<Vec<(&str, &str)>, AocError> {
+    input.split_ascii_whitespace().map(|field| get_field(input, field)).collect()
+}
+
+/// Splits a single `name:value` field, which has to be a slice of `input`
+fn get_field<'a>(input: &str, field: &'a str) -> Result<(&'a str, &'a str), AocError> {
+    field.split_once(':')
+        .ok_or_else(|| AocError::parse_at(DAY, input, field, format!("Expected 'name:value', found '{}'", field)))
+}
+
+/// Counts the passports of the batch that have all fields required by the schema and, if `check_data` is set, only
+/// valid values
+pub fn count_matching_passports(batch_file: &str, schema: &Schema, check_data: bool) -> Result<usize, AocError> {
+    let mut count = 0;
+    for passport in blocks(batch_file) {
+        let fields = get_fields(passport).map_err(|e| e.relative_to(batch_file, passport))?;
+        let is_valid = if check_data { schema.is_valid(&fields) } else { schema.is_complete(&fields) };
+        if is_valid {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+pub fn count_valid_passports(batch_file: &str, check_data: bool) -> Result<usize, AocError> {
+    count_matching_passports(batch_file, &NORTH_POLE, check_data)
+}
+
+pub struct Day4 {
+    input: String,
+}
+
+impl Solver for Day4 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day4 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(count_valid_passports(&self.input, false)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(count_valid_passports(&self.input, true)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema::{FieldSpec, Rule};
+
+    fn is_valid_passport(input: &str) -> Result<bool, AocError> {
+        Ok(NORTH_POLE.is_complete(&get_fields(input)?))
+    }
+
+    fn is_valid_passport_data(input: &str) -> Result<bool, AocError> {
+        Ok(NORTH_POLE.has_valid_values(&get_fields(input)?))
+    }
+
+    #[test]
+    fn test_is_valid_passport() {
+        let input = r"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+        let mut iter = input.split("\n\n");
+        assert_eq!(true, is_valid_passport(iter.next().unwrap()).unwrap());
+        assert_eq!(false, is_valid_passport(iter.next().unwrap()).unwrap());
+        assert_eq!(true, is_valid_passport(iter.next().unwrap()).unwrap());
+        assert_eq!(false, is_valid_passport(iter.next().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_count_valid_passports() {
+        let input = r"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+        assert_eq!(2, count_valid_passports(input, false).unwrap());
+    }
+
+    #[test]
+    fn test_part1() {
+        println!("{}", count_valid_passports(std::fs::read_to_string("resources/day4.txt").unwrap().as_str(), false).unwrap());
+    }
+
+    #[test]
+    fn test_part2_examples() {
+        assert_eq!(false, is_valid_passport_data(r"eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926").unwrap());
+        assert_eq!(false, is_valid_passport_data(r"iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946").unwrap());
+        assert_eq!(false, is_valid_passport_data(r"hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277").unwrap());
+        assert_eq!(false, is_valid_passport_data(r"hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007").unwrap());
+        assert_eq!(true, is_valid_passport_data(r"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f").unwrap());
+        assert_eq!(true, is_valid_passport_data(r"eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm").unwrap());
+        assert_eq!(true, is_valid_passport_data(r"hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022").unwrap());
+        assert_eq!(true, is_valid_passport_data(r"
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719").unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        println!("{}", count_valid_passports(std::fs::read_to_string("resources/day4.txt").unwrap().as_str(), true).unwrap());
+    }
+
+    #[test]
+    fn test_custom_schema() {
+        let input = "ecl:gry pid:860033327\n\necl:grn\n\npid:12";
+        let schema = Schema::new(vec![FieldSpec::required("pid", Rule::pattern(r"\d+").unwrap()), FieldSpec::optional("ecl", Rule::Any)]);
+        assert_eq!(2, count_matching_passports(input, &schema, true).unwrap());
+        assert_eq!(0, count_matching_passports(input, &Schema::north_pole(), false).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_field() {
+        assert_eq!(Err(AocError::parse(4, 3, 13, "Expected 'name:value', found 'iyr2013'")),
+                   count_valid_passports("ecl:gry pid:860033327\n\nhcl:#ae17e1 iyr2013", false));
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::day4::{get_fields, DAY};
+use crate::error::AocError;
+use crate::input::blocks;
+
+/// Names of the fields of the North Pole passports, in the order of the batch format
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+/// Passport as found in the batch file. Missing fields are `None`. The values are not validated, so that records
+/// that violate the rules can be exported as well, and fields other than those of the North Pole are kept in `extra`
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Passport {
+    pub byr: Option<String>,
+    pub iyr: Option<String>,
+    pub eyr: Option<String>,
+    pub hgt: Option<String>,
+    pub hcl: Option<String>,
+    pub ecl: Option<String>,
+    pub pid: Option<String>,
+    pub cid: Option<String>,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl Passport {
+    /// Parses the `name:value` pairs of a single passport. Duplicate fields are rejected, as are empty values, which
+    /// CSV couldn't tell apart from missing fields
+    pub fn parse(input: &str) -> Result<Passport, AocError> {
+        let mut passport = Passport::default();
+        for (name, value) in get_fields(input)? {
+            if value.is_empty() {
+                return Err(AocError::parse_at(DAY, input, name, format!("Field '{}' has no value", name)));
+            }
+            if passport.set(name, value.to_owned()).is_some() {
+                return Err(AocError::parse_at(DAY, input, name, format!("Duplicate field '{}'", name)));
+            }
+        }
+        Ok(passport)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut Option<String>> {
+        match name {
+            "byr" => Some(&mut self.byr),
+            "iyr" => Some(&mut self.iyr),
+            "eyr" => Some(&mut self.eyr),
+            "hgt" => Some(&mut self.hgt),
+            "hcl" => Some(&mut self.hcl),
+            "ecl" => Some(&mut self.ecl),
+            "pid" => Some(&mut self.pid),
+            "cid" => Some(&mut self.cid),
+            _ => None,
+        }
+    }
+
+    /// Sets a field and returns its previous value
+    fn set(&mut self, name: &str, value: String) -> Option<String> {
+        match self.field_mut(name) {
+            Some(field) => field.replace(value),
+            None => self.extra.insert(name.to_owned(), value),
+        }
+    }
+
+    /// Returns the value of a field, which may also be one of the `extra` fields
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let known = [&self.byr, &self.iyr, &self.eyr, &self.hgt, &self.hcl, &self.ecl, &self.pid, &self.cid];
+        match FIELDS.iter().position(|&field| field == name) {
+            Some(idx) => known[idx].as_deref(),
+            None => self.extra.get(name).map(String::as_str),
+        }
+    }
+
+    /// Returns the value of a field as a year, or `None` if it is missing or not a number
+    pub fn year(&self, name: &str) -> Option<u32> {
+        self.get(name)?.parse().ok()
+    }
+
+    /// Returns the present fields as `name:value` pairs, the North Pole fields first in the order of the batch format,
+    /// then the `extra` fields by name
+    pub fn fields(&self) -> Vec<(&str, &str)> {
+        FIELDS.iter()
+            .filter_map(|&name| self.get(name).map(|value| (name, value)))
+            .chain(self.extra.iter().map(|(name, value)| (name.as_str(), value.as_str())))
+            .collect()
+    }
+}
+
+/// Parses all passports of a batch file
+pub fn parse_batch(batch_file: &str) -> Result<Vec<Passport>, AocError> {
+    blocks(batch_file)
+        .map(|passport| Passport::parse(passport).map_err(|e| e.relative_to(batch_file, passport)))
+        .collect()
+}
+
+/// Writes one JSON object per line
+pub fn write_json_lines(passports: &[Passport], mut writer: impl Write) -> Result<(), String> {
+    for passport in passports {
+        serde_json::to_writer(&mut writer, passport).map_err(|e| e.to_string())?;
+        writeln!(writer).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Reads passports written by `write_json_lines`. Empty lines are skipped
+pub fn read_json_lines(reader: impl BufRead) -> Result<Vec<Passport>, String> {
+    let mut passports = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        if !line.trim().is_empty() {
+            passports.push(serde_json::from_str(&line).map_err(|e| format!("Line {}: {}", idx + 1, e))?);
+        }
+    }
+    Ok(passports)
+}
+
+/// Writes the passports as CSV with a header. The North Pole fields come first, followed by a column for every
+/// `extra` field of any passport. Missing fields are empty
+pub fn write_csv(passports: &[Passport], writer: impl Write) -> Result<(), String> {
+    let extra: BTreeSet<&str> = passports.iter().flat_map(|passport| passport.extra.keys().map(String::as_str)).collect();
+    let header: Vec<&str> = FIELDS.iter().copied().chain(extra).collect();
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(&header).map_err(|e| e.to_string())?;
+    for passport in passports {
+        writer.write_record(header.iter().map(|&name| passport.get(name).unwrap_or(""))).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Reads passports written by `write_csv`. Empty cells are missing fields
+pub fn read_csv(reader: impl Read) -> Result<Vec<Passport>, String> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let header = reader.headers().map_err(|e| e.to_string())?.clone();
+    let mut passports = Vec::new();
+    for record in reader.records() {
+        let mut passport = Passport::default();
+        for (name, value) in header.iter().zip(record.map_err(|e| e.to_string())?.iter()) {
+            if !value.is_empty() && passport.set(name, value.to_owned()).is_some() {
+                return Err(format!("Duplicate column '{}'", name));
+            }
+        }
+        passports.push(passport);
+    }
+    Ok(passports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::schema::Schema;
+
+    const BATCH: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\n\
+                         iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\nhcl:#cfa07d byr:1929";
+
+    #[test]
+    fn test_parse_batch() {
+        let passports = parse_batch(BATCH).unwrap();
+        assert_eq!(2, passports.len());
+        assert_eq!(Passport {
+            byr: Some(String::from("1929")),
+            iyr: Some(String::from("2013")),
+            eyr: Some(String::from("2023")),
+            hgt: None,
+            hcl: Some(String::from("#cfa07d")),
+            ecl: Some(String::from("amb")),
+            pid: Some(String::from("028048884")),
+            cid: Some(String::from("350")),
+            extra: BTreeMap::new(),
+        }, passports[1]);
+        assert_eq!(Some(1929), passports[1].year("byr"));
+        assert!(Schema::north_pole().is_valid(&passports[0].fields()));
+        assert_eq!(vec!["byr", "iyr", "eyr", "hcl", "ecl", "pid", "cid"],
+                   passports[1].fields().iter().map(|&(name, _)| name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unchecked_values() {
+        let passports = parse_batch("byr:abc\n\niyr:2013 foo:1 bar:x").unwrap();
+        assert_eq!(Some("abc"), passports[0].get("byr"));
+        assert_eq!(None, passports[0].year("byr"));
+        assert_eq!(vec![("iyr", "2013"), ("bar", "x"), ("foo", "1")], passports[1].fields());
+        assert_eq!(Some("1"), passports[1].get("foo"));
+    }
+
+    #[test]
+    fn test_invalid_batch() {
+        assert_eq!(Err(AocError::parse(4, 2, 1, "Duplicate field 'byr'")), parse_batch("byr:1937\nbyr:1940"));
+        assert_eq!(Err(AocError::parse(4, 3, 16, "Duplicate field 'foo'")), parse_batch("byr:1937\n\nfoo:1 iyr:2013 foo:2"));
+        assert_eq!(Err(AocError::parse(4, 1, 10, "Expected 'name:value', found 'iyr2013'")), parse_batch("byr:1937 iyr2013"));
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let passports = parse_batch(BATCH).unwrap();
+        let mut output = Vec::new();
+        write_json_lines(&passports, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(2, output.lines().count());
+        assert!(output.starts_with("{\"byr\":\"1937\",\"iyr\":\"2017\",\"eyr\":\"2020\",\"hgt\":\"183cm\""));
+        assert_eq!(passports, read_json_lines(output.as_bytes()).unwrap());
+        let passports = parse_batch("byr:abc foo:1").unwrap();
+        let mut output = Vec::new();
+        write_json_lines(&passports, &mut output).unwrap();
+        assert_eq!(passports, read_json_lines(output.as_slice()).unwrap());
+        assert!(read_json_lines("{}\n\n{\"byr\":1}".as_bytes()).unwrap_err().starts_with("Line 3: invalid type"));
+    }
+
+    #[test]
+    fn test_csv() {
+        let passports = parse_batch(BATCH).unwrap();
+        let mut output = Vec::new();
+        write_csv(&passports, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!("byr,iyr,eyr,hgt,hcl,ecl,pid,cid\n\
+                    1937,2017,2020,183cm,#fffffd,gry,860033327,147\n\
+                    1929,2013,2023,,#cfa07d,amb,028048884,350\n", output);
+        assert_eq!(passports, read_csv(output.as_bytes()).unwrap());
+        let passports = parse_batch("byr:abc foo:1\n\nbar:2").unwrap();
+        let mut output = Vec::new();
+        write_csv(&passports, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!("byr,iyr,eyr,hgt,hcl,ecl,pid,cid,bar,foo\nabc,,,,,,,,,1\n,,,,,,,,2,\n", output);
+        assert_eq!(passports, read_csv(output.as_bytes()).unwrap());
+        // an empty value would be read back as a missing field
+        assert_eq!(Err(AocError::parse(4, 1, 10, "Field 'cid' has no value")), parse_batch("byr:1937 cid:"));
+    }
+
+    #[test]
+    fn test_input_round_trip() {
+        let passports = parse_batch(&std::fs::read_to_string("resources/day4.txt").unwrap()).unwrap();
+        let mut output = Vec::new();
+        write_csv(&passports, &mut output).unwrap();
+        assert_eq!(passports, read_csv(output.as_slice()).unwrap());
+    }
+}
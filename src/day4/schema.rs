@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::input::read_file;
+
+/// Constraint on the value of a passport field
+#[derive(Debug, Clone)]
+pub enum Rule {
+    /// Any value is allowed
+    Any,
+    /// Integer within the range
+    Range(RangeInclusive<u64>),
+    /// The whole value has to match the regular expression
+    Pattern(Regex),
+    /// One of the listed values
+    OneOf(Vec<String>),
+    /// Integer followed by a unit, where each unit has its own range, e.g. `183cm`
+    Measure(Vec<(String, RangeInclusive<u64>)>),
+}
+
+impl Rule {
+    /// Creates a `Pattern` rule that matches the whole value
+    pub fn pattern(pattern: &str) -> Result<Rule, String> {
+        Regex::new(&format!("^(?:{})$", pattern))
+            .map(Rule::Pattern)
+            .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+    }
+
+    /// Returns why the value violates the rule, or `None` if it is valid
+    pub fn violation(&self, value: &str) -> Option<String> {
+        match self {
+            Rule::Any => None,
+            Rule::Range(range) => check_range(value, range),
+            Rule::Pattern(regex) if regex.is_match(value) => None,
+            Rule::Pattern(regex) => Some(format!("'{}' doesn't match {}", value, regex)),
+            Rule::OneOf(values) if values.iter().any(|allowed| allowed == value) => None,
+            Rule::OneOf(values) => Some(format!("'{}' is not one of {}", value, values.join(", "))),
+            Rule::Measure(units) => {
+                match units.iter().find(|(unit, _)| value.ends_with(unit.as_str())) {
+                    Some((unit, range)) => check_range(&value[..value.len() - unit.len()], range),
+                    None => {
+                        let units: Vec<&str> = units.iter().map(|(unit, _)| unit.as_str()).collect();
+                        Some(format!("'{}' has no unit, expected one of {}", value, units.join(", ")))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_range(value: &str, range: &RangeInclusive<u64>) -> Option<String> {
+    match value.parse::<u64>() {
+        Ok(number) if range.contains(&number) => None,
+        Ok(number) => Some(format!("{} is not between {} and {}", number, range.start(), range.end())),
+        Err(_) => Some(format!("'{}' is not a number", value)),
+    }
+}
+
+/// Describes one field of a passport
+#[derive(Debug, Clone)]
+pub struct FieldSpec {
+    pub name: String,
+    pub required: bool,
+    pub rule: Rule,
+}
+
+impl FieldSpec {
+    pub fn required(name: &str, rule: Rule) -> FieldSpec {
+        FieldSpec { name: name.to_owned(), required: true, rule }
+    }
+
+    pub fn optional(name: &str, rule: Rule) -> FieldSpec {
+        FieldSpec { name: name.to_owned(), required: false, rule }
+    }
+}
+
+/// Fields a passport may contain and the rules for their values. Fields that are not part of the schema are unknown
+#[derive(Debug, Clone)]
+pub struct Schema {
+    fields: Vec<FieldSpec>,
+}
+
+impl Schema {
+    pub fn new(fields: Vec<FieldSpec>) -> Schema {
+        Schema { fields }
+    }
+
+    /// The rules of the North Pole passport office, which also accepts North Pole Credentials without a `cid`
+    pub fn north_pole() -> Schema {
+        let range = |name, range| FieldSpec::required(name, Rule::Range(range));
+        Schema::new(vec![
+            range("byr", 1920..=2002),
+            range("iyr", 2010..=2020),
+            range("eyr", 2020..=2030),
+            FieldSpec::required("hgt", Rule::Measure(vec![(String::from("cm"), 150..=193), (String::from("in"), 59..=76)])),
+            FieldSpec::required("hcl", Rule::pattern("#[0-9a-f]{6}").unwrap()),
+            FieldSpec::required("ecl", Rule::OneOf(["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].iter().map(|&c| c.to_owned()).collect())),
+            FieldSpec::required("pid", Rule::pattern(r"\d{9}").unwrap()),
+            FieldSpec::optional("cid", Rule::Any),
+        ])
+    }
+
+    /// Parses a schema from JSON, see `load`
+    pub fn from_json(json: &str) -> Result<Schema, String> {
+        let config: SchemaConfig = serde_json::from_str(json).map_err(|e| format!("Invalid schema: {}", e))?;
+        let fields = config.fields.into_iter().map(FieldConfig::into_spec).collect::<Result<_, _>>()?;
+        Ok(Schema::new(fields))
+    }
+
+    /// Loads a schema from a JSON file of the form
+    /// `{"fields": [{"name": "byr", "range": [1920, 2002]}, {"name": "cid", "required": false}, ...]}`.
+    /// Fields are required unless stated otherwise and take at most one of the rules `range`, `pattern`, `one_of`
+    /// and `units` (a map from unit to range). Fields without a rule accept any value
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let json = read_file(path)?;
+        Schema::from_json(&json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn fields(&self) -> &[FieldSpec] {
+        &self.fields
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Checks that all required fields are present
+    pub fn is_complete(&self, fields: &[(&str, &str)]) -> bool {
+        self.fields.iter()
+            .filter(|spec| spec.required)
+            .all(|spec| fields.iter().any(|&(name, _)| name == spec.name))
+    }
+
+    /// Checks the values of the fields that are part of the schema. Unknown fields are ignored
+    pub fn has_valid_values(&self, fields: &[(&str, &str)]) -> bool {
+        fields.iter().all(|&(name, value)| self.get(name).filter(|spec| spec.rule.violation(value).is_some()).is_none())
+    }
+
+    pub fn is_valid(&self, fields: &[(&str, &str)]) -> bool {
+        self.is_complete(fields) && self.has_valid_values(fields)
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::north_pole()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaConfig {
+    fields: Vec<FieldConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldConfig {
+    name: String,
+    #[serde(default = "FieldConfig::default_required")]
+    required: bool,
+    range: Option<(u64, u64)>,
+    pattern: Option<String>,
+    one_of: Option<Vec<String>>,
+    units: Option<BTreeMap<String, (u64, u64)>>,
+}
+
+impl FieldConfig {
+    fn default_required() -> bool {
+        true
+    }
+
+    fn into_spec(self) -> Result<FieldSpec, String> {
+        let mut rules = Vec::new();
+        if let Some((min, max)) = self.range {
+            rules.push(Rule::Range(min..=max));
+        }
+        if let Some(pattern) = &self.pattern {
+            rules.push(Rule::pattern(pattern).map_err(|e| format!("Field '{}': {}", self.name, e))?);
+        }
+        if let Some(values) = self.one_of {
+            rules.push(Rule::OneOf(values));
+        }
+        if let Some(units) = self.units {
+            rules.push(Rule::Measure(units.into_iter().map(|(unit, (min, max))| (unit, min..=max)).collect()));
+        }
+        if rules.len() > 1 {
+            return Err(format!("Field '{}' has more than one rule", self.name));
+        }
+        Ok(FieldSpec { name: self.name, required: self.required, rule: rules.pop().unwrap_or(Rule::Any) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_valid(name: &str, value: &str) -> bool {
+        Schema::north_pole().get(name).unwrap().rule.violation(value).is_none()
+    }
+
+    #[test]
+    fn test_north_pole_rules() {
+        assert!(is_valid("byr", "2002"));
+        assert!(!is_valid("byr", "2003"));
+        assert!(!is_valid("byr", "abc"));
+        assert!(is_valid("hgt", "60in"));
+        assert!(is_valid("hgt", "190cm"));
+        assert!(!is_valid("hgt", "190in"));
+        assert!(!is_valid("hgt", "190"));
+        assert!(!is_valid("hgt", "cm"));
+        assert!(is_valid("hcl", "#123abc"));
+        assert!(!is_valid("hcl", "#123abz"));
+        assert!(!is_valid("hcl", "123abc"));
+        assert!(is_valid("ecl", "brn"));
+        assert!(!is_valid("ecl", "wat"));
+        assert!(is_valid("pid", "000000001"));
+        assert!(!is_valid("pid", "0123456789"));
+        assert!(is_valid("cid", "anything"));
+    }
+
+    #[test]
+    fn test_violations() {
+        let schema = Schema::north_pole();
+        let violation = |name, value| schema.get(name).unwrap().rule.violation(value);
+        assert_eq!(Some(String::from("2003 is not between 1920 and 2002")), violation("byr", "2003"));
+        assert_eq!(Some(String::from("'12a' is not a number")), violation("iyr", "12a"));
+        assert_eq!(Some(String::from("'190' has no unit, expected one of cm, in")), violation("hgt", "190"));
+        assert_eq!(Some(String::from("'wat' is not one of amb, blu, brn, gry, grn, hzl, oth")), violation("ecl", "wat"));
+        assert_eq!(Some(String::from("'123' doesn't match ^(?:\\d{9})$")), violation("pid", "123"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = Schema::new(vec![FieldSpec::required("a", Rule::Range(1..=3)), FieldSpec::optional("b", Rule::Any)]);
+        assert!(schema.is_valid(&[("a", "2")]));
+        assert!(schema.is_valid(&[("a", "3"), ("b", "x"), ("c", "x")]));
+        assert!(!schema.is_complete(&[("b", "x")]));
+        assert!(schema.has_valid_values(&[("b", "x")]));
+        assert!(!schema.has_valid_values(&[("a", "4")]));
+    }
+
+    #[test]
+    fn test_from_json() {
+        let schema = Schema::from_json(r#"{"fields": [
+            {"name": "age", "range": [18, 99]},
+            {"name": "size", "units": {"m": [1, 3]}},
+            {"name": "code", "pattern": "[A-Z]{2}", "required": false},
+            {"name": "eyes", "one_of": ["blue"]},
+            {"name": "note"}
+        ]}"#).unwrap();
+        assert_eq!(vec!["age", "size", "code", "eyes", "note"], schema.fields().iter().map(|field| field.name.as_str()).collect::<Vec<_>>());
+        assert!(schema.is_valid(&[("age", "20"), ("size", "2m"), ("eyes", "blue"), ("note", "")]));
+        assert!(!schema.is_valid(&[("age", "20"), ("size", "2m"), ("eyes", "blue"), ("note", ""), ("code", "ABC")]));
+        assert!(!schema.is_valid(&[("age", "20"), ("size", "2m"), ("eyes", "blue")]));
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert_eq!(Err(String::from("Field 'a' has more than one rule")),
+                   Schema::from_json(r#"{"fields": [{"name": "a", "range": [1, 2], "one_of": ["1"]}]}"#).map(|_| ()));
+        assert!(Schema::from_json(r#"{"fields": [{"name": "a", "pattern": "("}]}"#).unwrap_err().starts_with("Field 'a': Invalid pattern '('"));
+        assert!(Schema::from_json(r#"{"fields": [{"name": "a", "regex": "a"}]}"#).unwrap_err().starts_with("Invalid schema: unknown field `regex`"));
+    }
+
+    #[test]
+    fn test_load() {
+        let schema = Schema::load(Path::new("resources/day4_schema.json")).unwrap();
+        assert_eq!(Schema::north_pole().fields().len(), schema.fields().len());
+        let passport = [("byr", "1937"), ("iyr", "2017"), ("eyr", "2020"), ("hgt", "183cm"), ("hcl", "#fffffd"),
+            ("ecl", "gry"), ("pid", "860033327")];
+        assert!(schema.is_valid(&passport));
+        assert!(!schema.is_valid(&passport[1..]));
+        assert!(Schema::load(Path::new("resources/missing.json")).unwrap_err().starts_with("Error reading resources/missing.json"));
+    }
+}
@@ -1,40 +1,195 @@
-use std::error::Error;
-
-fn get_seat_id(boarding_pass: &str) -> Result<usize, Box<dyn Error>> {
-    let (row, col) = boarding_pass.split_at(7);
-    let row = usize::from_str_radix(
-        row.replace('F', "0")
-            .replace('B', "1").as_str(), 2)?;
-    let col = usize::from_str_radix(
-        col.replace('L', "0")
-            .replace('R', "1").as_str(), 2)?;
-    Ok(8 * row + col)
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 5;
+
+/// Layout of the plane. The first `row_bits` characters of a boarding pass select the row (`F` for the front half,
+/// `B` for the back half), the remaining `column_bits` characters select the column (`L` for left, `R` for right)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Layout {
+    row_bits: usize,
+    column_bits: usize,
+}
+
+impl Layout {
+    /// 128 rows of 8 seats
+    pub const STANDARD: Layout = Layout { row_bits: 7, column_bits: 3 };
+
+    /// Most bits a boarding pass may have, so that the seat map of a `Cabin` stays small
+    pub const MAX_BITS: usize = 24;
+
+    /// Fails if the boarding passes have more than `MAX_BITS` characters
+    pub fn new(row_bits: usize, column_bits: usize) -> Result<Layout, AocError> {
+        row_bits.checked_add(column_bits)
+            .filter(|&bits| bits <= Layout::MAX_BITS)
+            .map(|_| Layout { row_bits, column_bits })
+            .ok_or_else(|| AocError::no_solution(DAY, format!("A plane with {} row and {} column bits is too large, the limit is {} bits",
+                                                              row_bits, column_bits, Layout::MAX_BITS)))
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> usize {
+        1 << self.column_bits
+    }
+
+    pub fn pass_length(&self) -> usize {
+        self.row_bits + self.column_bits
+    }
+
+    /// Returns the id of the seat, which is `row * columns + column`
+    pub fn seat_id(&self, row: usize, column: usize) -> Option<usize> {
+        if row < self.rows() && column < self.columns() { Some(row * self.columns() + column) } else { None }
+    }
+
+    /// Returns the row and column of the seat with the given id
+    pub fn seat(&self, seat_id: usize) -> Option<(usize, usize)> {
+        if seat_id < self.rows() * self.columns() { Some((seat_id / self.columns(), seat_id % self.columns())) } else { None }
+    }
+
+    /// Decodes a boarding pass into the id of its seat
+    pub fn decode(&self, boarding_pass: &str) -> Result<usize, AocError> {
+        if boarding_pass.chars().count() != self.pass_length() {
+            return Err(AocError::parse(DAY, 1, 1,
+                format!("Expected {} characters, found '{}'", self.pass_length(), boarding_pass)));
+        }
+        let mut seat_id = 0;
+        for (i, c) in boarding_pass.chars().enumerate() {
+            let bit = match (i < self.row_bits, c) {
+                (true, 'F') | (false, 'L') => 0,
+                (true, 'B') | (false, 'R') => 1,
+                _ => return Err(AocError::parse(DAY, 1, i + 1, format!("Unexpected character '{}'", c))),
+            };
+            seat_id = seat_id * 2 + bit;
+        }
+        Ok(seat_id)
+    }
+
+    /// Returns the boarding pass of the seat with the given id, or `None` if the plane has no such seat
+    pub fn encode(&self, seat_id: usize) -> Option<String> {
+        self.seat(seat_id)?;
+        let pass = (0..self.pass_length()).rev()
+            .enumerate()
+            .map(|(i, bit)| match (i < self.row_bits, seat_id >> bit & 1 == 1) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            })
+            .collect();
+        Some(pass)
+    }
+
+    /// Returns the boarding pass of the seat in the given row and column
+    pub fn encode_seat(&self, row: usize, column: usize) -> Option<String> {
+        self.encode(self.seat_id(row, column)?)
+    }
+}
+
+fn get_seat_id(boarding_pass: &str) -> Result<usize, AocError> {
+    Layout::STANDARD.decode(boarding_pass)
 }
 
-pub fn get_highest_id(boarding_passes: &str) -> Result<usize, Box<dyn Error>> {
-    Ok(boarding_passes.trim().split('\n')
-        .filter_map(|pass| get_seat_id(pass).ok())
-        .max().ok_or("Error calculating max")?)
+fn get_seat_ids(boarding_passes: &str) -> Result<Vec<usize>, AocError> {
+    lines(boarding_passes)
+        .map(|pass| get_seat_id(pass).map_err(|e| e.relative_to(boarding_passes, pass)))
+        .collect()
 }
 
-pub fn get_missing_id(boarding_passes: &str) -> Result<usize, &str> {
-    let mut passes: Vec<usize> = boarding_passes.trim().split('\n')
-        .filter_map(|pass| get_seat_id(pass).ok())
-        .collect();
-    passes.sort_unstable();
-    let mut previous_seat = *passes.get(0).ok_or("test")?;
-    for seat in passes.iter().skip(1) {
-        if previous_seat + 1 != *seat {
-            return Ok(*seat - 1);
+pub fn get_highest_id(boarding_passes: &str) -> Result<usize, AocError> {
+    get_seat_ids(boarding_passes)?.into_iter()
+        .max().ok_or_else(|| AocError::no_solution(DAY, "There are no boarding passes"))
+}
+
+/// Occupancy of the seats of a plane, built from all boarding passes
+pub struct Cabin {
+    layout: Layout,
+    seats: Grid<bool>,
+    duplicates: Vec<usize>,
+}
+
+impl Cabin {
+    pub fn new(layout: Layout, boarding_passes: &str) -> Result<Cabin, AocError> {
+        let mut seats = Grid::filled(layout.columns(), layout.rows(), false);
+        let mut duplicates = Vec::new();
+        for pass in lines(boarding_passes) {
+            let seat_id = layout.decode(pass).map_err(|e| e.relative_to(boarding_passes, pass))?;
+            let (row, column) = layout.seat(seat_id).unwrap();
+            if std::mem::replace(&mut seats[(column, row)], true) && !duplicates.contains(&seat_id) {
+                duplicates.push(seat_id);
+            }
         }
-        previous_seat = *seat;
+        Ok(Cabin { layout, seats, duplicates })
+    }
+
+    pub fn is_occupied(&self, seat_id: usize) -> bool {
+        self.layout.seat(seat_id).is_some_and(|(row, column)| self.seats[(column, row)])
+    }
+
+    /// Returns the ids of all empty seats in ascending order
+    pub fn empty_seats(&self) -> Vec<usize> {
+        self.seats.positions()
+            .filter(|&position| !self.seats[position])
+            .map(|(column, row)| self.layout.seat_id(row, column).unwrap())
+            .collect()
+    }
+
+    /// Returns the ids of the empty seats whose neighbours by id, `id - 1` and `id + 1`, are both occupied
+    pub fn gaps(&self) -> Vec<usize> {
+        self.empty_seats().into_iter()
+            .filter(|&seat_id| seat_id > 0 && self.is_occupied(seat_id - 1) && self.is_occupied(seat_id + 1))
+            .collect()
+    }
+
+    /// Returns the ids of the seats with more than one boarding pass, in the order of the second pass
+    pub fn duplicates(&self) -> &[usize] {
+        &self.duplicates
+    }
+
+    /// Draws the cabin from front to back, one row per line, with `#` for occupied and `.` for empty seats
+    pub fn render(&self) -> String {
+        self.seats.map(|&occupied| if occupied { '#' } else { '.' }).to_string()
+    }
+}
+
+pub fn get_missing_id(boarding_passes: &str) -> Result<usize, AocError> {
+    let cabin = Cabin::new(Layout::STANDARD, boarding_passes)?;
+    if cabin.empty_seats().len() == Layout::STANDARD.rows() * Layout::STANDARD.columns() {
+        return Err(AocError::no_solution(DAY, "There are no boarding passes"));
+    }
+    cabin.gaps().first().copied().ok_or_else(|| AocError::no_solution(DAY, "No gap found"))
+}
+
+pub struct Day5 {
+    input: String,
+}
+
+impl Solver for Day5 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day5 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_highest_id(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_missing_id(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
     }
-    Err("no gap found")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day5::{get_seat_id, get_highest_id, get_missing_id};
+    use crate::day5::{get_seat_id, get_highest_id, get_missing_id, Cabin, Layout};
+    use crate::error::AocError;
 
     #[test]
     fn test_get_seat_id() {
@@ -44,6 +199,36 @@ mod tests {
         assert_eq!(820, get_seat_id("BBFFBBFRLL").unwrap());
     }
 
+    #[test]
+    fn test_encode() {
+        assert_eq!(Some(String::from("FBFBBFFRLR")), Layout::STANDARD.encode(357));
+        assert_eq!(Some(String::from("BBFFBBFRLL")), Layout::STANDARD.encode_seat(102, 4));
+        assert_eq!(Some(String::from("FFFFFFFLLL")), Layout::STANDARD.encode(0));
+        assert_eq!(Some(String::from("BBBBBBBRRR")), Layout::STANDARD.encode(1023));
+        assert_eq!(None, Layout::STANDARD.encode(1024));
+        assert_eq!(None, Layout::STANDARD.encode_seat(3, 8));
+        for seat_id in 0..1024 {
+            assert_eq!(Ok(seat_id), get_seat_id(&Layout::STANDARD.encode(seat_id).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        let layout = Layout::new(2, 2).unwrap();
+        assert_eq!((4, 4, 4), (layout.rows(), layout.columns(), layout.pass_length()));
+        assert_eq!(Some(14), layout.seat_id(3, 2));
+        assert_eq!(Some((3, 2)), layout.seat(14));
+        assert_eq!(Some(String::from("BBRL")), layout.encode(14));
+        assert_eq!(Ok(14), layout.decode("BBRL"));
+        assert_eq!(Err(AocError::parse(5, 1, 3, "Unexpected character 'F'")), layout.decode("BBFL"));
+        assert_eq!(Err(AocError::parse(5, 1, 1, "Expected 4 characters, found 'FBFBBFFRLR'")), layout.decode("FBFBBFFRLR"));
+        assert_eq!(Some(String::from("LR")), Layout::new(0, 2).unwrap().encode(1));
+        assert!(Layout::new(12, 12).is_ok());
+        assert_eq!(Err(AocError::no_solution(5, "A plane with 20 row and 20 column bits is too large, the limit is 24 bits")),
+                   Layout::new(20, 20));
+        assert!(Layout::new(usize::MAX, 1).is_err());
+    }
+
     #[test]
     fn test_get_highest_id() {
         let passes = r"FBFBBFFRLR
@@ -54,6 +239,33 @@ BBFFBBFRLL
         assert_eq!(820, get_highest_id(passes).unwrap());
     }
 
+    #[test]
+    fn test_invalid_boarding_pass() {
+        assert_eq!(Err(AocError::parse(5, 2, 8, "Unexpected character 'B'")), get_highest_id("FBFBBFFRLR\nBFFFBBFBRR\n"));
+        assert_eq!(Err(AocError::parse(5, 1, 1, "Expected 10 characters, found 'FBF'")), get_seat_id("FBF"));
+    }
+
+    #[test]
+    fn test_cabin() {
+        let layout = Layout::new(2, 2).unwrap();
+        let passes = "FFLL\nFFRL\nFBLL\nFFRL\nFBLR\nFFRL\nBFRR";
+        let cabin = Cabin::new(layout, passes).unwrap();
+        assert_eq!("#.#.\n##..\n...#\n....", cabin.render());
+        assert_eq!(vec![1, 3, 6, 7, 8, 9, 10, 12, 13, 14, 15], cabin.empty_seats());
+        assert_eq!(vec![1, 3], cabin.gaps());
+        assert_eq!(&[2], cabin.duplicates());
+        assert!(cabin.is_occupied(11));
+        assert!(!cabin.is_occupied(16));
+    }
+
+    #[test]
+    fn test_get_missing_id() {
+        assert_eq!(Ok(2), get_missing_id(&["FFFFFFFLLR", "FFFFFFFLRR"].join("\n")));
+        assert_eq!(Err(AocError::no_solution(5, "No gap found")), get_missing_id("FFFFFFFLLR\nFFFFFFFLRL"));
+        assert_eq!(Err(AocError::no_solution(5, "There are no boarding passes")), get_missing_id(""));
+        assert_eq!(Err(AocError::parse(5, 2, 1, "Expected 10 characters, found 'FBF'")), get_missing_id("FFFFFFFLLR\nFBF"));
+    }
+
     #[test]
     fn test_part1() {
         println!("{}", get_highest_id(std::fs::read_to_string("resources/day5.txt").unwrap().as_str()).unwrap())
@@ -1,18 +1,153 @@
-use std::collections::{HashSet, HashMap};
+use std::collections::HashSet;
+use crate::error::AocError;
+use crate::input::{blocks, lines};
+use crate::solver::{Answer, Solver};
 
-fn count_distinct_answers(group: &str) -> usize {
-    group.chars().filter(|c| c.is_ascii_alphabetic()).collect::<HashSet<_>>().len()
+const DAY: u8 = 6;
+
+/// Answers are given as lowercase letters, one line per person
+fn check_answers(input: &str) -> Result<(), AocError> {
+    for (i, line) in input.lines().enumerate() {
+        if let Some((column, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(AocError::parse(DAY, i + 1, column + 1, format!("Unexpected character '{}'", c)));
+        }
+    }
+    Ok(())
+}
+
+/// Questions a person answered with "yes". Bit `i` stands for the question `'a' + i`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    /// All 26 questions
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    /// Collects the lowercase letters of `answers`, other characters are ignored
+    pub fn parse(answers: &str) -> AnswerSet {
+        AnswerSet(answers.chars().filter(char::is_ascii_lowercase).fold(0, |set, c| set | 1 << (c as u8 - b'a')))
+    }
+
+    pub fn contains(self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 & 1 << (question as u8 - b'a') != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+
+    /// Returns the questions in alphabetical order
+    pub fn questions(self) -> impl Iterator<Item=char> {
+        (b'a'..=b'z').map(char::from).filter(move |&question| self.contains(question))
+    }
+}
+
+/// How many people of a group need to answer a question with "yes" for it to count
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Quorum {
+    /// At least the given number of people
+    People(usize),
+    /// At least the given percentage of the group, rounded up to whole people
+    Percent(usize),
+}
+
+impl Quorum {
+    /// Anyone in the group answered the question
+    pub const ANYONE: Quorum = Quorum::People(1);
+    /// Everyone in the group answered the question
+    pub const EVERYONE: Quorum = Quorum::Percent(100);
+
+    /// Returns the number of people needed in a group of the given size. A question always needs at least one "yes"
+    pub fn threshold(self, group_size: usize) -> usize {
+        let people = match self {
+            Quorum::People(people) => people,
+            Quorum::Percent(percent) => (group_size * percent).div_ceil(100),
+        };
+        people.max(1)
+    }
+}
+
+/// Answers of a group, one set per person
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Group {
+    people: Vec<AnswerSet>,
+}
+
+impl Group {
+    /// Parses a group with the answers of one person per line
+    pub fn parse(group: &str) -> Group {
+        Group { people: lines(group).map(AnswerSet::parse).collect() }
+    }
+
+    pub fn size(&self) -> usize {
+        self.people.len()
+    }
+
+    /// Returns how many people answered each question, index 0 is question `a`
+    pub fn histogram(&self) -> [usize; 26] {
+        let mut histogram = [0; 26];
+        for person in &self.people {
+            for question in person.questions() {
+                histogram[(question as u8 - b'a') as usize] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Returns the questions that reached the quorum
+    pub fn answered(&self, quorum: Quorum) -> AnswerSet {
+        match quorum.threshold(self.size()) {
+            1 => self.people.iter().fold(AnswerSet::default(), |set, &person| set.union(person)),
+            threshold if threshold == self.size() => self.people.iter().fold(AnswerSet::ALL, |set, &person| set.intersection(person)),
+            threshold => {
+                let histogram = self.histogram();
+                AnswerSet((0..26).filter(|&idx| histogram[idx] >= threshold).fold(0, |set, idx| set | 1 << idx))
+            }
+        }
+    }
+}
+
+/// Parses all groups, which are separated by empty lines
+pub fn parse_groups(input: &str) -> Result<Vec<Group>, AocError> {
+    check_answers(input)?;
+    Ok(blocks(input).map(Group::parse).collect())
+}
+
+/// Sums the number of questions per group that reached the quorum
+pub fn sum_answered(input: &str, quorum: Quorum) -> Result<usize, AocError> {
+    Ok(parse_groups(input)?.iter().map(|group| group.answered(quorum).len()).sum())
+}
+
+/// Returns how many people answered each question over all groups, index 0 is question `a`
+pub fn get_histogram(input: &str) -> Result<[usize; 26], AocError> {
+    let mut histogram = [0; 26];
+    for group in parse_groups(input)? {
+        for (total, count) in histogram.iter_mut().zip(group.histogram().iter()) {
+            *total += count;
+        }
+    }
+    Ok(histogram)
 }
 
-pub fn get_sum_of_distinct_answers(input: &str) -> usize {
-    input.trim().split("\n\n").map(|group| count_distinct_answers(group)).sum()
+pub fn get_sum_of_distinct_answers(input: &str) -> Result<usize, AocError> {
+    sum_answered(input, Quorum::ANYONE)
 }
 
 #[allow(dead_code)]
 fn part1_oneliner(input: &str) -> usize {
-    input
-        .trim()
-        .split("\n\n")
+    blocks(input)
         .map(|group| {
             group
                 .chars()
@@ -23,30 +158,44 @@ fn part1_oneliner(input: &str) -> usize {
         .sum()
 }
 
-fn count_common_answers(group: &str) -> usize {
-    let mut num_participants = 0;
-    let mut distinct_answers: HashMap<char, usize> = HashMap::new();
-    for participant in group.split('\n') {
-        num_participants += 1;
-        for chr in participant.chars() {
-            let val = match distinct_answers.get(&chr) {
-                None => { 1 }
-                Some(answer) => { answer + 1 }
-            };
-            distinct_answers.insert(chr, val);
-        }
-    }
-    distinct_answers.iter().filter(|(_, &v)| v == num_participants).count()
+pub fn get_sum_of_common_answers(input: &str) -> Result<usize, AocError> {
+    sum_answered(input, Quorum::EVERYONE)
 }
 
-pub fn get_sum_of_common_answers(input: &str) -> usize {
-    input.trim().split("\n\n").map(|group| count_common_answers(group)).sum()
+pub struct Day6 {
+    input: String,
+}
+
+impl Solver for Day6 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day6 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_sum_of_distinct_answers(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_sum_of_common_answers(&self.input)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn count_distinct_answers(group: &str) -> usize {
+        Group::parse(group).answered(Quorum::ANYONE).len()
+    }
+
+    fn count_common_answers(group: &str) -> usize {
+        Group::parse(group).answered(Quorum::EVERYONE).len()
+    }
+
     #[test]
     fn example_part1() {
         let input = r"abc
@@ -73,14 +222,14 @@ b
         assert_eq!(1, count_distinct_answers(iter.next().unwrap()));
         assert_eq!(None, iter.next());
 
-        assert_eq!(11, get_sum_of_distinct_answers(input));
+        assert_eq!(11, get_sum_of_distinct_answers(input).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day6.txt")
             .expect("Error reading file to string");
-        println!("{}", get_sum_of_distinct_answers(input.as_str()));
+        println!("{}", get_sum_of_distinct_answers(input.as_str()).unwrap());
         println!("{}", part1_oneliner(input.as_str()));
     }
 
@@ -110,13 +259,56 @@ b
         assert_eq!(1, count_common_answers(iter.next().unwrap()));
         assert_eq!(None, iter.next());
 
-        assert_eq!(6, get_sum_of_common_answers(input));
+        assert_eq!(6, get_sum_of_common_answers(input).unwrap());
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day6.txt")
             .expect("Error reading file to string");
-        println!("{}", get_sum_of_common_answers(input.as_str()));
+        println!("{}", get_sum_of_common_answers(input.as_str()).unwrap());
+    }
+
+    #[test]
+    fn test_answer_set() {
+        let set = AnswerSet::parse("zab");
+        assert_eq!(3, set.len());
+        assert!(set.contains('z') && !set.contains('c') && !set.contains('A'));
+        assert_eq!(vec!['a', 'b', 'z'], set.questions().collect::<Vec<_>>());
+        assert_eq!(AnswerSet::parse("b"), set.intersection(AnswerSet::parse("bc")));
+        assert_eq!(AnswerSet::parse("abcz"), set.union(AnswerSet::parse("bc")));
+        assert!(AnswerSet::parse("").is_empty());
+        assert_eq!(26, AnswerSet::ALL.len());
+    }
+
+    #[test]
+    fn test_quorum() {
+        let group = Group::parse("abc\nab\na\nd");
+        assert_eq!(4, group.size());
+        assert_eq!(&[3, 2, 1, 1, 0], &group.histogram()[..5]);
+        assert_eq!(AnswerSet::parse("abcd"), group.answered(Quorum::ANYONE));
+        assert_eq!(AnswerSet::parse("ab"), group.answered(Quorum::People(2)));
+        assert_eq!(AnswerSet::parse("a"), group.answered(Quorum::People(3)));
+        assert_eq!(AnswerSet::parse(""), group.answered(Quorum::EVERYONE));
+        assert_eq!(AnswerSet::parse("a"), group.answered(Quorum::Percent(51)));
+        assert_eq!(AnswerSet::parse("ab"), group.answered(Quorum::Percent(50)));
+        assert_eq!(AnswerSet::parse("abcd"), group.answered(Quorum::Percent(0)));
+        assert_eq!(AnswerSet::parse(""), group.answered(Quorum::People(5)));
+    }
+
+    #[test]
+    fn test_sum_answered() {
+        let input = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+        assert_eq!(11, sum_answered(input, Quorum::ANYONE).unwrap());
+        assert_eq!(6, sum_answered(input, Quorum::EVERYONE).unwrap());
+        assert_eq!(8, sum_answered(input, Quorum::Percent(50)).unwrap());
+        let histogram = get_histogram(input).unwrap();
+        assert_eq!(&[8, 4, 3, 0], &histogram[..4]);
+        assert_eq!(15, histogram.iter().sum::<usize>());
+    }
+
+    #[test]
+    fn test_invalid_answer() {
+        assert_eq!(Err(AocError::parse(6, 3, 2, "Unexpected character 'B'")), get_sum_of_common_answers("abc\n\naBc\n"));
     }
 }
\ No newline at end of file
@@ -0,0 +1,293 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::day7::{parse_rules, DAY};
+use crate::day7::validation::find_problems;
+use crate::error::AocError;
+
+/// Bag rules as a graph. The nodes are the colours, an edge from an outer to an inner colour is weighted with the
+/// number of inner bags the outer bag has to contain
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BagGraph {
+    colours: Vec<String>,
+    ids: HashMap<String, usize>,
+    contents: Vec<Vec<(usize, usize)>>,
+    containers: Vec<Vec<usize>>,
+}
+
+impl BagGraph {
+    /// Parses rules of the form `<colour> bags contain <n> <colour> bag(s), ...` or `<colour> bags contain no other
+    /// bags`, one per line. Every colour needs exactly one rule, and no bag may contain itself. The first problem
+    /// found by `validate_rules` is returned as error
+    pub fn parse(input: &str) -> Result<BagGraph, AocError> {
+        let rules = parse_rules(input)?;
+        if let Some(problem) = find_problems(input, &rules).first() {
+            return Err(problem.to_error());
+        }
+        let colours: Vec<String> = rules.iter().map(|rule| rule.outer.to_owned()).collect();
+        let ids: HashMap<String, usize> = colours.iter().enumerate().map(|(id, colour)| (colour.clone(), id)).collect();
+        let mut contents = vec![Vec::new(); colours.len()];
+        let mut containers = vec![Vec::new(); colours.len()];
+        for (outer, rule) in rules.iter().enumerate() {
+            for &(count, colour) in &rule.contents {
+                let inner = ids[colour];
+                contents[outer].push((inner, count));
+                containers[inner].push(outer);
+            }
+        }
+        Ok(BagGraph { colours, ids, contents, containers })
+    }
+
+    /// Returns the colours in the order of their rules
+    pub fn colours(&self) -> impl Iterator<Item=&str> {
+        self.colours.iter().map(String::as_str)
+    }
+
+    fn id(&self, colour: &str) -> Result<usize, AocError> {
+        self.ids.get(colour).copied()
+            .ok_or_else(|| AocError::no_solution(DAY, format!("There is no rule for {} bags", colour)))
+    }
+
+    /// Returns the colours and numbers of the bags directly inside a bag of the given colour
+    pub fn contents(&self, colour: &str) -> Result<Vec<(&str, usize)>, AocError> {
+        Ok(self.contents[self.id(colour)?].iter().map(|&(inner, count)| (self.colours[inner].as_str(), count)).collect())
+    }
+
+    /// Counts all bags inside a bag of the given colour. The count of every colour is only computed once. Fails if the
+    /// number of bags overflows
+    pub fn count_contained(&self, colour: &str) -> Result<usize, AocError> {
+        let outer = self.id(colour)?;
+        let mut counts = vec![None; self.colours.len()];
+        // the path holds the bags and the index of the next content to count, a bag is counted after all its contents
+        let mut path = vec![(outer, 0)];
+        while let Some((bag, next_content)) = path.last_mut() {
+            let bag = *bag;
+            match self.contents[bag].get(*next_content) {
+                Some(&(inner, _)) => {
+                    *next_content += 1;
+                    if counts[inner].is_none() {
+                        path.push((inner, 0));
+                    }
+                }
+                None => {
+                    counts[bag] = Some(self.count_inside(bag, &counts)?);
+                    path.pop();
+                }
+            }
+        }
+        Ok(counts[outer].unwrap())
+    }
+
+    /// Counts the bags inside `outer` from the counts of the bags it contains
+    fn count_inside(&self, outer: usize, counts: &[Option<usize>]) -> Result<usize, AocError> {
+        self.contents[outer].iter()
+            .try_fold(0usize, |total, &(inner, count)| {
+                counts[inner].unwrap().checked_add(1)
+                    .and_then(|bags| bags.checked_mul(count))
+                    .and_then(|bags| total.checked_add(bags))
+            })
+            .ok_or_else(|| AocError::no_solution(DAY, "The number of bags overflows"))
+    }
+
+    /// Returns the colours of all bags that eventually contain a bag of the given colour, in the order of their rules
+    pub fn containers(&self, colour: &str) -> Result<Vec<&str>, AocError> {
+        let mut seen = vec![false; self.colours.len()];
+        let mut pending = vec![self.id(colour)?];
+        while let Some(inner) = pending.pop() {
+            for &outer in &self.containers[inner] {
+                if !seen[outer] {
+                    seen[outer] = true;
+                    pending.push(outer);
+                }
+            }
+        }
+        Ok(self.colours().zip(seen).filter(|&(_, seen)| seen).map(|(colour, _)| colour).collect())
+    }
+
+    /// Returns a shortest chain of bags from `outer` down to `target`, starting with `(outer, 1)`. Each further step
+    /// holds a colour and how many bags of it the previous bag contains, so the product of the numbers is the number of
+    /// `target` bags along the chain. Returns `None` if an `outer` bag can't contain a `target` bag
+    pub fn containment_chain(&self, outer: &str, target: &str) -> Result<Option<Vec<(&str, usize)>>, AocError> {
+        let (outer, target) = (self.id(outer)?, self.id(target)?);
+        // previous bag and count on the shortest chain to each bag
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.colours.len()];
+        let mut queue = VecDeque::from(vec![outer]);
+        let mut seen = vec![false; self.colours.len()];
+        seen[outer] = true;
+        while let Some(bag) = queue.pop_front() {
+            if bag == target {
+                let mut chain = Vec::new();
+                let mut current = target;
+                while let Some((parent, count)) = previous[current] {
+                    chain.push((self.colours[current].as_str(), count));
+                    current = parent;
+                }
+                chain.push((self.colours[outer].as_str(), 1));
+                chain.reverse();
+                return Ok(Some(chain));
+            }
+            for &(inner, count) in &self.contents[bag] {
+                if !seen[inner] {
+                    seen[inner] = true;
+                    previous[inner] = Some((bag, count));
+                    queue.push_back(inner);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the graph in the DOT language of Graphviz, with an edge from each bag to the bags it contains, labelled
+    /// with their number. With a `root`, only the bags inside a `root` bag are included
+    pub fn to_dot(&self, root: Option<&str>) -> Result<String, AocError> {
+        let mut included = vec![root.is_none(); self.colours.len()];
+        if let Some(root) = root {
+            let mut pending = vec![self.id(root)?];
+            while let Some(bag) = pending.pop() {
+                if !std::mem::replace(&mut included[bag], true) {
+                    pending.extend(self.contents[bag].iter().map(|&(inner, _)| inner));
+                }
+            }
+        }
+        let quote = |id: usize| format!("\"{}\"", self.colours[id].replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("digraph bags {\n");
+        for id in (0..self.colours.len()).filter(|&id| included[id]) {
+            dot += &format!("    {};\n", quote(id));
+        }
+        for outer in (0..self.colours.len()).filter(|&id| included[id]) {
+            for &(inner, count) in &self.contents[outer] {
+                dot += &format!("    {} -> {} [label={}];\n", quote(outer), quote(inner), count);
+            }
+        }
+        dot.push('}');
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    #[test]
+    fn test_parse() {
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!(9, graph.colours().count());
+        assert_eq!(Some("light red"), graph.colours().next());
+        assert_eq!(vec![("shiny gold", 2), ("faded blue", 9)], graph.contents("muted yellow").unwrap());
+        assert!(graph.contents("faded blue").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_count_contained() {
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!(32, graph.count_contained("shiny gold").unwrap());
+        assert_eq!(11, graph.count_contained("vibrant plum").unwrap());
+        assert_eq!(0, graph.count_contained("dotted black").unwrap());
+    }
+
+    #[test]
+    fn test_count_contained_deep_rules() {
+        // the number of paths from c0 to c60 doubles with every level, so this doesn't finish without memoization
+        let rules: Vec<String> = (0..60)
+            .flat_map(|idx| vec![
+                format!("colour c{} bags contain 1 colour c{} bag, 1 colour d{} bag.", idx, idx + 1, idx + 1),
+                format!("colour d{} bags contain 1 colour c{} bag.", idx + 1, idx + 1),
+            ])
+            .chain(std::iter::once(String::from("colour c60 bags contain no other bags.")))
+            .collect();
+        let graph = BagGraph::parse(&rules.join("\n")).unwrap();
+        assert_eq!(3 * ((1 << 60) - 1), graph.count_contained("colour c0").unwrap());
+        assert_eq!(3, graph.count_contained("colour c59").unwrap());
+        assert_eq!(120, graph.containers("colour c60").unwrap().len());
+    }
+
+    #[test]
+    fn test_count_contained_overflow() {
+        let rules: Vec<String> = (0..70)
+            .flat_map(|idx| vec![
+                format!("colour c{} bags contain 1 colour c{} bag, 1 colour d{} bag.", idx, idx + 1, idx + 1),
+                format!("colour d{} bags contain 1 colour c{} bag.", idx + 1, idx + 1),
+            ])
+            .chain(std::iter::once(String::from("colour c70 bags contain no other bags.")))
+            .collect();
+        let graph = BagGraph::parse(&rules.join("\n")).unwrap();
+        assert_eq!(Err(AocError::no_solution(7, "The number of bags overflows")), graph.count_contained("colour c0"));
+        assert_eq!(3 * ((1 << 10) - 1), graph.count_contained("colour c60").unwrap());
+    }
+
+    #[test]
+    fn test_count_contained_long_chain() {
+        let rules: Vec<String> = (0..200_000)
+            .map(|idx| format!("colour c{} bags contain 1 colour c{} bag.", idx, idx + 1))
+            .chain(std::iter::once(String::from("colour c200000 bags contain no other bags.")))
+            .collect();
+        let graph = BagGraph::parse(&rules.join("\n")).unwrap();
+        assert_eq!(200_000, graph.count_contained("colour c0").unwrap());
+    }
+
+    #[test]
+    fn test_containers() {
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!(vec!["light red", "dark orange", "bright white", "muted yellow"], graph.containers("shiny gold").unwrap());
+        assert!(graph.containers("light red").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unknown_colours() {
+        assert_eq!(Err(AocError::parse(7, 2, 25, "There is no rule for dark orange bags")),
+                   BagGraph::parse("shiny gold bags contain 2 dark red bags.\ndark red bags contain 2 dark orange bags."));
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!(Err(AocError::no_solution(7, "There is no rule for plaid green bags")), graph.count_contained("plaid green"));
+        assert!(graph.containers("plaid green").is_err());
+        assert!(graph.contents("plaid green").is_err());
+    }
+
+    #[test]
+    fn test_containment_chain() {
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!(Some(vec![("light red", 1), ("bright white", 1), ("shiny gold", 1), ("vibrant plum", 2)]),
+                   graph.containment_chain("light red", "vibrant plum").unwrap());
+        assert_eq!(Some(vec![("dark olive", 1)]), graph.containment_chain("dark olive", "dark olive").unwrap());
+        assert_eq!(None, graph.containment_chain("shiny gold", "light red").unwrap());
+        assert!(graph.containment_chain("shiny gold", "plaid green").is_err());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = BagGraph::parse(RULES).unwrap();
+        assert_eq!("digraph bags {
+    \"shiny gold\";
+    \"dark olive\";
+    \"vibrant plum\";
+    \"faded blue\";
+    \"dotted black\";
+    \"shiny gold\" -> \"dark olive\" [label=1];
+    \"shiny gold\" -> \"vibrant plum\" [label=2];
+    \"dark olive\" -> \"faded blue\" [label=3];
+    \"dark olive\" -> \"dotted black\" [label=4];
+    \"vibrant plum\" -> \"faded blue\" [label=5];
+    \"vibrant plum\" -> \"dotted black\" [label=6];
+}", graph.to_dot(Some("shiny gold")).unwrap());
+        let dot = graph.to_dot(None).unwrap();
+        assert_eq!(9 + 13 + 2, dot.lines().count());
+        assert!(dot.contains("    \"light red\" -> \"muted yellow\" [label=2];\n"));
+        assert!(graph.to_dot(Some("plaid green")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert_eq!(Err(AocError::parse(7, 1, 1, "dark red bags contain themselves: dark red -> shiny gold -> dark red")),
+                   BagGraph::parse("dark red bags contain 1 shiny gold bag.\nshiny gold bags contain 2 dark red bags."));
+        assert_eq!(Err(AocError::parse(7, 2, 1, "There is already a rule for dark red bags on line 1")),
+                   BagGraph::parse("dark red bags contain no other bags.\ndark red bags contain no other bags."));
+    }
+}
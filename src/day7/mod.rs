@@ -0,0 +1,140 @@
+pub mod graph;
+pub mod validation;
+
+use regex::Regex;
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+use graph::BagGraph;
+
+const DAY: u8 = 7;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(r"(?:(\w+ \w+) bag)").unwrap();
+    static ref RE2: Regex = Regex::new(r"(?:(\d+ \w+ \w+) bag)").unwrap();
+}
+
+/// Returns the colour of the outer bag the rule is about
+fn get_outer_bag<'a>(input: &str, row: &'a str) -> Result<&'a str, AocError> {
+    RE.captures(row).filter(|c| c.get(0).unwrap().start() == 0)
+        .map(|c| c.get(1).unwrap().as_str())
+        .ok_or_else(|| AocError::parse_at(DAY, input, row,
+            format!("Expected '<colour> bags contain ...', found '{}'", row)))
+}
+
+/// Rule for the bags of one colour. The colours are slices of the input, so they can be used to locate errors
+struct Rule<'a> {
+    outer: &'a str,
+    contents: Vec<(usize, &'a str)>,
+}
+
+fn parse_rules(input: &str) -> Result<Vec<Rule<'_>>, AocError> {
+    let mut rules = Vec::new();
+    for row in lines(input) {
+        let outer = get_outer_bag(input, row)?;
+        let mut contents = Vec::new();
+        for bag in RE2.captures_iter(row) {
+            let (amount, colour) = bag.get(1).unwrap().as_str().split_once(' ').unwrap();
+            contents.push((parse_fragment(DAY, input, amount)?, colour));
+        }
+        rules.push(Rule { outer, contents });
+    }
+    Ok(rules)
+}
+
+pub fn get_number_of_possible_bags(input: &str, search: &str) -> Result<usize, AocError> {
+    Ok(BagGraph::parse(input)?.containers(search)?.len())
+}
+
+pub fn get_number_of_contained_bags(input: &str, bag: &str) -> Result<usize, AocError> {
+    BagGraph::parse(input)?.count_contained(bag)
+}
+
+pub struct Day7 {
+    graph: BagGraph,
+}
+
+impl Solver for Day7 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day7 { graph: BagGraph::parse(input)? })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(self.graph.containers("shiny gold")?.len().into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(self.graph.count_contained("shiny gold")?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 9 faded blue bags, 2 shiny gold bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+        assert_eq!(4, get_number_of_possible_bags(input, "shiny gold").unwrap());
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = std::fs::read_to_string("resources/day7.txt")
+            .expect("Error reading file to string");
+        println!("{}", get_number_of_possible_bags(input.as_str(), "shiny gold").unwrap());
+    }
+
+    #[test]
+    fn test_part2_examples() {
+        let input = r"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+        assert_eq!(126, get_number_of_contained_bags(input, "shiny gold").unwrap());
+
+        let input = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+        assert_eq!(32, get_number_of_contained_bags(input, "shiny gold").unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = std::fs::read_to_string("resources/day7.txt")
+            .expect("Error reading file to string");
+        println!("{}", get_number_of_contained_bags(input.as_str(), "shiny gold").unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "shiny gold bags contain 2 dark red bags.\ndark red bags contain 2 dark orange bags.\n";
+        assert_eq!(Err(AocError::parse(7, 2, 25, "There is no rule for dark orange bags")),
+                   get_number_of_contained_bags(input, "shiny gold"));
+        assert_eq!(Err(AocError::parse(7, 2, 1, "Expected '<colour> bags contain ...', found 'contain no other bags.'")),
+                   get_number_of_possible_bags("shiny gold bags contain 2 dark red bags.\ncontain no other bags.", "shiny gold"));
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::day7::{parse_rules, Rule, DAY};
+use crate::error::{locate, AocError};
+
+/// Inconsistency in otherwise well-formed bag rules
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RuleProblem {
+    /// A bag contains bags of a colour that has no rule
+    Undefined { colour: String, line: usize, column: usize },
+    /// There is more than one rule for the colour
+    Duplicate { colour: String, line: usize, first_line: usize },
+    /// Bags that eventually contain themselves. The colours are listed along the cycle, starting with the colour whose
+    /// rule is on `line`
+    Cycle { colours: Vec<String>, line: usize },
+}
+
+impl RuleProblem {
+    pub fn to_error(&self) -> AocError {
+        match self {
+            RuleProblem::Undefined { colour, line, column } =>
+                AocError::parse(DAY, *line, *column, format!("There is no rule for {} bags", colour)),
+            RuleProblem::Duplicate { colour, line, first_line } =>
+                AocError::parse(DAY, *line, 1, format!("There is already a rule for {} bags on line {}", colour, first_line)),
+            RuleProblem::Cycle { colours, line } =>
+                AocError::parse(DAY, *line, 1, format!("{} bags contain themselves: {} -> {}", colours[0], colours.join(" -> "), colours[0])),
+        }
+    }
+}
+
+impl fmt::Display for RuleProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_error())
+    }
+}
+
+/// Checks the rules for colours with several rules, undefined colours and cycles. Only syntax errors are returned as
+/// an error. The problems are grouped by kind, in this order
+pub fn validate_rules(input: &str) -> Result<Vec<RuleProblem>, AocError> {
+    Ok(find_problems(input, &parse_rules(input)?))
+}
+
+pub(super) fn find_problems(input: &str, rules: &[Rule]) -> Vec<RuleProblem> {
+    let mut problems = Vec::new();
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    for (id, rule) in rules.iter().enumerate() {
+        if let Some(&first) = ids.get(rule.outer) {
+            problems.push(RuleProblem::Duplicate {
+                colour: rule.outer.to_owned(),
+                line: locate(input, rule.outer).0,
+                first_line: locate(input, rules[first].outer).0,
+            });
+        } else {
+            ids.insert(rule.outer, id);
+        }
+    }
+    // only the first rule of a colour counts, and edges to undefined colours are left out
+    let mut edges = vec![Vec::new(); rules.len()];
+    for (id, rule) in rules.iter().enumerate().filter(|&(id, rule)| ids[rule.outer] == id) {
+        for &(_, colour) in &rule.contents {
+            match ids.get(colour) {
+                Some(&inner) => edges[id].push(inner),
+                None => {
+                    let (line, column) = locate(input, colour);
+                    problems.push(RuleProblem::Undefined { colour: colour.to_owned(), line, column });
+                }
+            }
+        }
+    }
+    for cycle in find_cycles(&edges) {
+        problems.push(RuleProblem::Cycle {
+            colours: cycle.iter().map(|&id| rules[id].outer.to_owned()).collect(),
+            line: locate(input, rules[cycle[0]].outer).0,
+        });
+    }
+    problems
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    New,
+    OnPath,
+    Done,
+}
+
+/// Finds a cycle for every edge that leads back to a node on the current path of a depth-first search. Each cycle
+/// starts with the node the edge leads to
+fn find_cycles(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut cycles = Vec::new();
+    let mut states = vec![State::New; edges.len()];
+    for start in 0..edges.len() {
+        if states[start] != State::New {
+            continue;
+        }
+        // the path holds the nodes and the index of the next edge to follow
+        let mut path = vec![(start, 0)];
+        states[start] = State::OnPath;
+        while let Some((node, next_edge)) = path.last_mut() {
+            let node = *node;
+            match edges[node].get(*next_edge) {
+                Some(&next) => {
+                    *next_edge += 1;
+                    match states[next] {
+                        State::New => {
+                            states[next] = State::OnPath;
+                            path.push((next, 0));
+                        }
+                        State::OnPath => {
+                            let from = path.iter().position(|&(node, _)| node == next).unwrap();
+                            cycles.push(path[from..].iter().map(|&(node, _)| node).collect());
+                        }
+                        State::Done => {}
+                    }
+                }
+                None => {
+                    states[node] = State::Done;
+                    path.pop();
+                }
+            }
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_rules() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+bright white bags contain 1 muted yellow bag.
+muted yellow bags contain no other bags.";
+        assert_eq!(Ok(Vec::new()), validate_rules(input));
+    }
+
+    #[test]
+    fn test_problems() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+shiny gold bags contain 3 light red bags, 1 dark olive bag.
+muted yellow bags contain 1 muted yellow bag.
+light red bags contain no other bags.";
+        assert_eq!(Ok(vec![
+            RuleProblem::Duplicate { colour: String::from("light red"), line: 5, first_line: 1 },
+            RuleProblem::Undefined { colour: String::from("dark olive"), line: 3, column: 45 },
+            RuleProblem::Cycle { colours: vec![String::from("light red"), String::from("bright white"), String::from("shiny gold")], line: 1 },
+            RuleProblem::Cycle { colours: vec![String::from("muted yellow")], line: 4 },
+        ]), validate_rules(input));
+    }
+
+    #[test]
+    fn test_display() {
+        let cycle = RuleProblem::Cycle { colours: vec![String::from("light red"), String::from("shiny gold")], line: 3 };
+        assert_eq!("Day 7, line 3, column 1: light red bags contain themselves: light red -> shiny gold -> light red", cycle.to_string());
+        let duplicate = RuleProblem::Duplicate { colour: String::from("light red"), line: 5, first_line: 1 };
+        assert_eq!(AocError::parse(7, 5, 1, "There is already a rule for light red bags on line 1"), duplicate.to_error());
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        assert_eq!(Err(AocError::parse(7, 1, 1, "Expected '<colour> bags contain ...', found 'bags'")), validate_rules("bags"));
+    }
+}
@@ -1,6 +1,11 @@
 use std::collections::HashSet;
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
 
-#[derive(Debug, PartialEq)]
+const DAY: u8 = 8;
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Instruction {
     operation: Operation,
     argument: isize,
@@ -23,45 +28,48 @@ impl Instruction {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 enum Operation {
     ACC,
     JMP,
     NOP,
 }
 
-pub fn parse_input(input: &str) -> Vec<Instruction> {
-    input.trim().split('\n')
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, AocError> {
+    lines(input)
         .map(|x| {
-            let (op, arg) = x.split_once(' ').unwrap();
-            Instruction {
+            let (op, arg) = x.split_once(' ')
+                .ok_or_else(|| AocError::parse_at(DAY, input, x, format!("Expected '<operation> <argument>', found '{}'", x)))?;
+            Ok(Instruction {
                 operation: match op {
                     "acc" => Operation::ACC,
                     "jmp" => Operation::JMP,
-                    &_ => Operation::NOP
+                    "nop" => Operation::NOP,
+                    &_ => return Err(AocError::parse_at(DAY, input, op, format!("Unknown operation '{}'", op))),
                 },
-                argument: arg.parse().unwrap(),
-            }
+                argument: parse_fragment(DAY, input, arg)?,
+            })
         })
         .collect()
 }
 
-pub fn execute(instructions: &[Instruction]) -> isize {
+pub fn execute(instructions: &[Instruction]) -> Result<isize, AocError> {
     let mut pointer: isize = 0;
     let mut accumulator: isize = 0;
     let mut visited_instructions: HashSet<usize> = HashSet::new();
     loop {
-        let instr = instructions.get(pointer as usize).unwrap();
+        let instr = instructions.get(pointer as usize)
+            .ok_or_else(|| AocError::no_solution(DAY, format!("The programme jumps to {} without looping", pointer)))?;
         let (new_accumulator, pointer_offset) = instr.execute(accumulator);
         pointer += pointer_offset;
         if !visited_instructions.insert(pointer as usize) {
-            return accumulator;
+            return Ok(accumulator);
         }
         accumulator = new_accumulator;
     }
 }
 
-pub fn execute2(mut instructions: Vec<Instruction>) -> isize {
+pub fn execute2(mut instructions: Vec<Instruction>) -> Result<isize, AocError> {
     for i in 0..instructions.len() {
         let mut pointer: isize = 0;
         let mut accumulator: isize = 0;
@@ -72,14 +80,17 @@ pub fn execute2(mut instructions: Vec<Instruction>) -> isize {
             instructions.get_mut(i).unwrap().operation = Operation::NOP;
         }
         loop {
-            let instr = instructions.get(pointer as usize).unwrap();
+            let instr = &instructions[pointer as usize];
             let (new_accumulator, pointer_offset) = instr.execute(accumulator);
             pointer += pointer_offset;
             if !visited_instructions.insert(pointer as usize) {
                 break;
             }
-            if (pointer as usize) >= instructions.len() {
-                return new_accumulator;
+            if pointer as usize == instructions.len() {
+                return Ok(new_accumulator);
+            }
+            if pointer < 0 || pointer as usize > instructions.len() {
+                break;
             }
             accumulator = new_accumulator;
         }
@@ -89,7 +100,29 @@ pub fn execute2(mut instructions: Vec<Instruction>) -> isize {
             instructions.get_mut(i).unwrap().operation = Operation::NOP;
         }
     }
-    panic!("problem");
+    Err(AocError::no_solution(DAY, "No single changed instruction makes the programme terminate"))
+}
+
+pub struct Day8 {
+    instructions: Vec<Instruction>,
+}
+
+impl Solver for Day8 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day8 { instructions: parse_input(input)? })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(execute(&self.instructions)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(execute2(self.instructions.clone())?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
@@ -114,7 +147,7 @@ jmp +4";
             operation: Operation::JMP,
             argument: 4,
         });
-        let out = parse_input(input);
+        let out = parse_input(input).unwrap();
         assert_eq!(res, out);
     }
 
@@ -141,8 +174,8 @@ acc -99
 acc +1
 jmp -4
 acc +6";
-        let instructions = parse_input(input);
-        assert_eq!(5, execute(&instructions));
+        let instructions = parse_input(input).unwrap();
+        assert_eq!(5, execute(&instructions).unwrap());
     }
 
     #[test]
@@ -156,21 +189,34 @@ acc -99
 acc +1
 jmp -4
 acc +6";
-        let instructions = parse_input(input);
-        assert_eq!(8, execute2(instructions));
+        let instructions = parse_input(input).unwrap();
+        assert_eq!(8, execute2(instructions).unwrap());
     }
 
     #[test]
     fn test_part1() {
         let input = std::fs::read_to_string("resources/day8.txt").unwrap();
-        let instructions = parse_input(input.as_str());
-        println!("{}", execute(&instructions));
+        let instructions = parse_input(input.as_str()).unwrap();
+        println!("{}", execute(&instructions).unwrap());
     }
 
     #[test]
     fn test_part2() {
         let input = std::fs::read_to_string("resources/day8.txt").unwrap();
-        let instructions = parse_input(input.as_str());
-        println!("{}", execute2(instructions));
+        let instructions = parse_input(input.as_str()).unwrap();
+        println!("{}", execute2(instructions).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Err(AocError::parse(8, 2, 1, "Unknown operation 'add'")), parse_input("nop +0\nadd +1"));
+        assert_eq!(Err(AocError::parse(8, 2, 5, "Invalid value '+x': invalid digit found in string")), parse_input("nop +0\nacc +x"));
+        assert_eq!(Err(AocError::parse(8, 1, 1, "Expected '<operation> <argument>', found 'nop'")), parse_input("nop"));
+    }
+
+    #[test]
+    fn test_no_solution() {
+        assert!(execute(&parse_input("acc +1\njmp +1").unwrap()).is_err());
+        assert!(execute2(parse_input("jmp +0\njmp -1").unwrap()).is_err());
+    }
+}
@@ -1,19 +1,26 @@
-pub fn get_error(input: &str, preamble_length: usize) -> usize {
-    let input = parse_input(input);
+use crate::error::{parse_fragment, AocError};
+use crate::input::lines;
+use crate::solver::{Answer, Solver};
+
+const DAY: u8 = 9;
+
+pub fn get_error(input: &str, preamble_length: usize) -> Result<usize, AocError> {
+    if preamble_length == 0 {
+        return Err(AocError::no_solution(DAY, "The preamble must not be empty"));
+    }
+    let input = parse_input(input)?;
     for (index, value) in input.iter().enumerate().skip(preamble_length) {
         if !is_sum_of_previous_elems(&input, preamble_length, index, *value) {
-            return *value;
+            return Ok(*value);
         }
     }
-    panic!("all numbers are good");
+    Err(AocError::no_solution(DAY, "All numbers are the sum of two of their predecessors"))
 }
 
-fn parse_input(input: &str) -> Vec<usize> {
-    let vec: Vec<usize> = input.trim()
-        .split('\n')
-        .map(|x| x.parse().unwrap())
-        .collect();
-    vec
+fn parse_input(input: &str) -> Result<Vec<usize>, AocError> {
+    lines(input)
+        .map(|x| parse_fragment(DAY, input, x))
+        .collect()
 }
 
 fn is_sum_of_previous_elems(data: &[usize], preamble_length: usize, index: usize, value: usize) -> bool {
@@ -27,8 +34,8 @@ fn is_sum_of_previous_elems(data: &[usize], preamble_length: usize, index: usize
     false
 }
 
-pub fn get_delta_of_contiguous_set(data: &str, target_sum: usize) -> usize {
-    let data = parse_input(data);
+pub fn get_delta_of_contiguous_set(data: &str, target_sum: usize) -> Result<usize, AocError> {
+    let data = parse_input(data)?;
 
     for start in 0..data.len() - 1 {
         let mut sum: usize = 0;
@@ -38,11 +45,33 @@ pub fn get_delta_of_contiguous_set(data: &str, target_sum: usize) -> usize {
             sum += val;
             if *val < min_val { min_val = *val; }
             if *val > max_val { max_val = *val; }
-            if sum == target_sum { return max_val + min_val; }
+            if sum == target_sum { return Ok(max_val + min_val); }
             if sum > target_sum { break; }
         }
     }
-    panic!("no suitable delta found");
+    Err(AocError::no_solution(DAY, format!("No contiguous set sums up to {}", target_sum)))
+}
+
+pub struct Day9 {
+    input: String,
+}
+
+impl Solver for Day9 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day9 { input: input.to_owned() })
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        Ok(get_error(&self.input, 25)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        Ok(get_delta_of_contiguous_set(&self.input, get_error(&self.input, 25)?)?.into())
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
 }
 
 #[cfg(test)]
@@ -72,12 +101,12 @@ mod tests {
 309
 576";
 
-        assert_eq!(127, get_error(input, 5));
+        assert_eq!(127, get_error(input, 5).unwrap());
     }
 
     #[test]
     fn test_part1() {
-        println!("part1: {}", get_error(std::fs::read_to_string("resources/day9.txt").unwrap().as_str(), 25));
+        println!("part1: {}", get_error(std::fs::read_to_string("resources/day9.txt").unwrap().as_str(), 25).unwrap());
     }
 
     #[test]
@@ -102,7 +131,7 @@ mod tests {
 277
 309
 576";
-        assert_eq!(62, get_delta_of_contiguous_set(input, 127));
+        assert_eq!(62, get_delta_of_contiguous_set(input, 127).unwrap());
     }
 
     #[test]
@@ -110,6 +139,6 @@ mod tests {
         let input = std::fs::read_to_string("resources/day9.txt").unwrap();
         println!("part2: {}",
                  get_delta_of_contiguous_set(input.as_str(),
-                                             get_error(input.as_str(), 25)));
+                                             get_error(input.as_str(), 25).unwrap()).unwrap());
     }
 }
\ No newline at end of file
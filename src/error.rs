@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error of any day, pointing to the offending part of the input where possible
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AocError {
+    /// The input is malformed. Line and column are 1-based, the column counts characters
+    Parse { day: u8, line: usize, column: usize, message: String },
+    /// The input is well-formed, but the puzzle has no solution for it
+    NoSolution { day: u8, message: String },
+    UnknownDay { day: u8 },
+    UnknownPart { day: u8, part: u8 },
+}
+
+impl AocError {
+    pub fn parse(day: u8, line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::Parse { day, line, column, message: message.into() }
+    }
+
+    /// Creates a parse error located at the start of `fragment`, which has to be a slice of `input`
+    pub fn parse_at(day: u8, input: &str, fragment: &str, message: impl Into<String>) -> AocError {
+        let (line, column) = locate(input, fragment);
+        AocError::parse(day, line, column, message)
+    }
+
+    /// Moves a parse error that was located relative to `fragment` to its position within `input`.
+    /// `fragment` has to be a slice of `input`
+    pub fn relative_to(self, input: &str, fragment: &str) -> AocError {
+        match self {
+            AocError::Parse { day, line, column, message } => {
+                let (fragment_line, fragment_column) = locate(input, fragment);
+                let column = if line == 1 { fragment_column + column - 1 } else { column };
+                AocError::parse(day, fragment_line + line - 1, column, message)
+            }
+            e => e,
+        }
+    }
+
+    pub fn no_solution(day: u8, message: impl Into<String>) -> AocError {
+        AocError::NoSolution { day, message: message.into() }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse { day, line, column, message } => {
+                write!(f, "Day {}, line {}, column {}: {}", day, line, column, message)
+            }
+            AocError::NoSolution { day, message } => write!(f, "Day {}: {}", day, message),
+            AocError::UnknownDay { day } => write!(f, "Day {} is not solved yet", day),
+            AocError::UnknownPart { day, part } => write!(f, "Day {} has no part {}", day, part),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+/// Returns the 1-based line and column at which `fragment` starts.
+/// If `fragment` is not a slice of `input`, the start of the input is returned
+pub fn locate(input: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    if offset > input.len() {
+        return (1, 1);
+    }
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+/// Parses `fragment`, which has to be a slice of `input`, into a value, reporting its location if it is invalid
+pub fn parse_fragment<T>(day: u8, input: &str, fragment: &str) -> Result<T, AocError>
+    where T: FromStr, T::Err: fmt::Display {
+    fragment.parse().map_err(|e| AocError::parse_at(day, input, fragment, format!("Invalid value '{}': {}", fragment, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "abc\ndéf\nghi";
+        assert_eq!((1, 1), locate(input, &input[0..3]));
+        assert_eq!((1, 3), locate(input, &input[2..3]));
+        assert_eq!((2, 1), locate(input, &input[4..]));
+        assert_eq!((2, 3), locate(input, &input[7..9]));
+        assert_eq!((3, 3), locate(input, &input[11..]));
+        assert_eq!((1, 1), locate(input, &String::from("abc")));
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let input = "abc\n  def\nghi";
+        let fragment = &input[6..];
+        assert_eq!(AocError::parse(1, 2, 4, "x"), AocError::parse(1, 1, 2, "x").relative_to(input, fragment));
+        assert_eq!(AocError::parse(1, 3, 2, "x"), AocError::parse(1, 2, 2, "x").relative_to(input, fragment));
+        assert_eq!(AocError::no_solution(1, "x"), AocError::no_solution(1, "x").relative_to(input, fragment));
+    }
+
+    #[test]
+    fn test_parse_fragment() {
+        let input = "acc +1\njmp x4";
+        assert_eq!(Ok(1), parse_fragment::<isize>(8, input, &input[4..6]));
+        assert_eq!(Err(AocError::parse(8, 2, 5, "Invalid value 'x4': invalid digit found in string")),
+                   parse_fragment::<isize>(8, input, &input[11..]));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("Day 11, line 2, column 4: Unknown character 'x'",
+                   AocError::parse(11, 2, 4, "Unknown character 'x'").to_string());
+        assert_eq!("Day 8: The programme does not terminate", AocError::no_solution(8, "The programme does not terminate").to_string());
+        assert_eq!("Day 25 has no part 2", AocError::UnknownPart { day: 25, part: 2 }.to_string());
+    }
+}
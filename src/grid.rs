@@ -0,0 +1,224 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::AocError;
+use crate::input;
+
+/// Offsets of the 4 orthogonal neighbours of a cell
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all 8 neighbours of a cell, including the diagonal ones
+pub const ADJACENT: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+/// Rectangular grid of cells, stored row by row. Positions are given as `(x, y)`, where `(0, 0)` is the top left cell
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, given row by row. Panics if the cells don't fill complete rows
+    pub fn new(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(width > 0 && cells.chunks_exact(width).remainder().is_empty(), "{} cells don't form rows of width {}", cells.len(), width);
+        Grid { width, height: cells.len() / width, cells }
+    }
+
+    /// Parses a rectangular map of characters. `cell` converts a character, or returns `None` if it is not allowed
+    pub fn parse(day: u8, input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, AocError> {
+        let rows = input::grid(day, input, cell)?;
+        let width = rows[0].len();
+        Ok(Grid::new(width, rows.into_iter().flatten().collect()))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height { self.cells.get(y * self.width + x) } else { None }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height { self.cells.get_mut(y * self.width + x) } else { None }
+    }
+
+    /// Returns all positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    /// Returns all cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item=&[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Moves from `position` by `offset`, returning `None` when leaving the grid
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = (x as isize).checked_add(dx).filter(|&x| x >= 0 && (x as usize) < self.width)?;
+        let y = (y as isize).checked_add(dy).filter(|&y| y >= 0 && (y as usize) < self.height)?;
+        Some((x as usize, y as usize))
+    }
+
+    /// Returns the positions of the up to 4 orthogonal neighbours
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Returns the positions of the up to 8 neighbours, including the diagonal ones
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        ADJACENT.iter().filter_map(move |&offset| self.step(position, offset))
+    }
+
+    /// Returns the positions passed when moving from `position` in the given direction until leaving the grid.
+    /// The start position is not included
+    pub fn ray(&self, position: (usize, usize), direction: (isize, isize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        std::iter::successors(self.step(position, direction), move |&position| self.step(position, direction))
+    }
+
+    /// Looks from `position` in all 8 directions and returns the first cell in each direction that satisfies
+    /// `is_visible`, checking at most `max_distance` cells per direction
+    pub fn ray_cast<'a>(&'a self, position: (usize, usize), max_distance: usize, is_visible: impl Fn(&T) -> bool + 'a)
+        -> impl Iterator<Item=(usize, usize)> + 'a {
+        ADJACENT.iter().filter_map(move |&direction| {
+            self.ray(position, direction).take(max_distance).find(|&position| is_visible(&self[position]))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::new(width, vec![value; width * height])
+    }
+
+    /// Returns the grid rotated clockwise by 90 degrees
+    pub fn rotate(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|position| self[position].clone())
+            .collect();
+        Grid::new(self.height, cells)
+    }
+
+    /// Returns the grid mirrored at its vertical axis, swapping left and right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let cells = self.rows().flat_map(|row| row.iter().rev().cloned()).collect();
+        Grid::new(self.width, cells)
+    }
+
+    /// Returns the grid mirrored at its horizontal axis, swapping top and bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let cells = self.rows().rev().flat_map(|row| row.iter().cloned()).collect();
+        Grid::new(self.width, cells)
+    }
+
+    /// Returns the part of the grid of the given size whose top left cell is at `(x, y)`
+    pub fn crop(&self, (x, y): (usize, usize), width: usize, height: usize) -> Grid<T> {
+        let cells = self.rows().skip(y).take(height).flat_map(|row| row[x..x + width].iter().cloned()).collect();
+        Grid::new(width, cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", position, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", position, width, height))
+    }
+}
+
+/// Prints the grid row by row, without a newline after the last row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse(0, "abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = get_grid();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!('f', grid[(2, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(vec!['b', 'e'], grid.column(1).copied().collect::<Vec<_>>());
+        assert_eq!(Err(AocError::parse(0, 2, 1, "Expected 3 columns, found 2")), Grid::parse(0, "abc\nde", Some));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = get_grid();
+        assert_eq!(vec![(0, 0), (1, 1)], grid.neighbours4((0, 1)).collect::<Vec<_>>());
+        assert_eq!(vec![(1, 0), (1, 1), (0, 1)], grid.neighbours8((0, 0)).collect::<Vec<_>>());
+        assert_eq!(8, Grid::filled(3, 3, 0).neighbours8((1, 1)).count());
+    }
+
+    #[test]
+    fn test_ray_cast() {
+        let grid = Grid::parse(0, "#.#.\n....\n#.L#", Some).unwrap();
+        assert_eq!(vec![(1, 0), (2, 0), (3, 0)], grid.ray((0, 0), (1, 0)).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 0), (2, 0), (3, 2), (0, 2)], grid.ray_cast((2, 2), usize::MAX, |&c| c == '#').collect::<Vec<_>>());
+        assert_eq!(vec![(3, 2)], grid.ray_cast((2, 2), 1, |&c| c == '#').collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid = get_grid();
+        assert_eq!("da\neb\nfc", grid.rotate().to_string());
+        assert_eq!(grid, grid.rotate().rotate().rotate().rotate());
+        assert_eq!("cba\nfed", grid.flip_horizontal().to_string());
+        assert_eq!("def\nabc", grid.flip_vertical().to_string());
+        assert_eq!("b\ne", grid.crop((1, 0), 1, 2).to_string());
+        assert_eq!("ef", grid.crop((1, 1), 2, 1).to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::new(2, vec![true, false, false, true]);
+        assert_eq!("#.\n.#", grid.map(|&active| if active { '#' } else { '.' }).to_string());
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use crate::error::{locate, AocError};
+
+/// Strips a byte order mark, converts CRLF line endings, and removes trailing whitespace of every line as well as
+/// trailing empty lines. Line numbers stay the same, so errors still point to the right line of the original input
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_owned()
+}
+
+/// Reads and normalizes a puzzle input
+pub fn read_file(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map(|input| normalize(&input))
+        .map_err(|e| format!("Error reading {}: {}", path.display(), e))
+}
+
+/// Returns the lines of the input without surrounding whitespace, skipping empty lines at the start and end.
+/// The lines are slices of `input`, so they can be used to locate errors
+pub fn lines(input: &str) -> impl Iterator<Item=&str> {
+    input.trim().lines().map(str::trim)
+}
+
+/// Returns the blocks of lines of a normalized input that are separated by one or more empty lines
+pub fn blocks(input: &str) -> impl Iterator<Item=&str> {
+    input.split("\n\n").map(str::trim).filter(|block| !block.is_empty())
+}
+
+/// Parses a rectangular map of characters row by row. `cell` converts a character, or returns `None` if the character
+/// is not allowed
+pub fn grid<T>(day: u8, input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Vec<Vec<T>>, AocError> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for row in lines(input) {
+        let mut cells = Vec::with_capacity(row.len());
+        for (column, c) in row.chars().enumerate() {
+            match cell(c) {
+                Some(value) => cells.push(value),
+                None => {
+                    let (line, row_column) = locate(input, row);
+                    return Err(AocError::parse(day, line, row_column + column, format!("Unknown character '{}'", c)));
+                }
+            }
+        }
+        if let Some(width) = rows.first().map(Vec::len) {
+            if cells.len() != width {
+                return Err(AocError::parse_at(day, input, row, format!("Expected {} columns, found {}", width, cells.len())));
+            }
+        }
+        rows.push(cells);
+    }
+    if rows.is_empty() {
+        return Err(AocError::parse(day, 1, 1, "The map is empty"));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("abc\n\n  def\nghi", normalize("\u{feff}abc \r\n\t\r\n  def\r\nghi  \r\n\r\n"));
+        assert_eq!("abc\ndef", normalize("abc\ndef\n"));
+        assert_eq!("", normalize("\n \n"));
+    }
+
+    #[test]
+    fn test_lines() {
+        let input = "\n  abc \ndef\n\nghi\n\n";
+        assert_eq!(vec!["abc", "def", "", "ghi"], lines(input).collect::<Vec<_>>());
+        assert_eq!(0, lines("").count());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "\nabc\ndef\n\n\n\nghi\n";
+        assert_eq!(vec!["abc\ndef", "ghi"], blocks(input).collect::<Vec<_>>());
+        assert_eq!(vec!["abc", "def"], blocks(&normalize("abc\r\n \r\ndef")).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_grid() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(Ok(vec![vec![true, false], vec![false, false]]), grid(3, "#.\n..\n", cell));
+        assert_eq!(Err(AocError::parse(3, 2, 2, "Unknown character 'x'")), grid(3, "#.\n.x", cell));
+        assert_eq!(Err(AocError::parse(3, 3, 1, "Expected 2 columns, found 3")), grid(3, "#.\n..\n...", cell));
+        assert_eq!(Err(AocError::parse(3, 1, 1, "The map is empty")), grid(3, "\n", cell));
+    }
+}
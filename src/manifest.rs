@@ -0,0 +1,127 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::input::read_file;
+use crate::registry::solve;
+
+/// Location of the manifest with the expected answers, relative to the crate root
+pub const DEFAULT_MANIFEST: &str = "resources/answers.txt";
+
+/// Known answer of one part for a given input
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Expectation {
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+/// Result of checking a solver against its expected answer
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Correct,
+    Wrong { actual: String },
+    Failed { message: String },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "ok"),
+            Outcome::Wrong { actual } => write!(f, "wrong answer {}", actual),
+            Outcome::Failed { message } => write!(f, "failed: {}", message),
+        }
+    }
+}
+
+impl Expectation {
+    /// Solves the part for the expected input and compares the result with the expected answer
+    pub fn verify(&self) -> Outcome {
+        let input = match read_file(&self.input) {
+            Ok(input) => input,
+            Err(message) => return Outcome::Failed { message },
+        };
+        self.compare(solve(self.day, self.part, &input).map(|answer| answer.to_string()).map_err(|e| e.to_string()))
+    }
+
+    /// Compares the result of a solver with the expected answer
+    pub fn compare(&self, result: Result<String, String>) -> Outcome {
+        match result {
+            Ok(actual) if actual == self.answer => Outcome::Correct,
+            Ok(actual) => Outcome::Wrong { actual },
+            Err(message) => Outcome::Failed { message },
+        }
+    }
+}
+
+/// Parses a manifest consisting of lines of the form `<day> <part> <input file> <answer>`.
+/// Empty lines and lines starting with '#' are ignored
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Expectation>, String> {
+    let mut expectations = Vec::new();
+    for (idx, line) in manifest.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = split_field(line)
+            .and_then(|(day, rest)| split_field(rest).map(|(part, rest)| (day, part, rest)))
+            .and_then(|(day, part, rest)| split_field(rest).map(|(input, answer)| (day, part, input, answer.trim())));
+        let (day, part, input, answer) = match fields {
+            Some(fields) => fields,
+            None => return Err(format!("Line {}: expected '<day> <part> <input file> <answer>', found '{}'", idx + 1, line)),
+        };
+        expectations.push(Expectation {
+            day: day.parse().map_err(|_| format!("Line {}: invalid day '{}'", idx + 1, day))?,
+            part: part.parse().map_err(|_| format!("Line {}: invalid part '{}'", idx + 1, part))?,
+            input: PathBuf::from(input),
+            answer: answer.to_owned(),
+        });
+    }
+    Ok(expectations)
+}
+
+/// Splits off the first whitespace-separated field
+fn split_field(line: &str) -> Option<(&str, &str)> {
+    line.trim_start().split_once(char::is_whitespace)
+}
+
+pub fn read_manifest(path: &Path) -> Result<Vec<Expectation>, String> {
+    let manifest = std::fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+    parse_manifest(&manifest).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "# comment\n\n1 1 resources/day1.txt 651651\n21 2\tinput.txt   a,b c\n";
+        let expected = vec![
+            Expectation { day: 1, part: 1, input: PathBuf::from("resources/day1.txt"), answer: String::from("651651") },
+            Expectation { day: 21, part: 2, input: PathBuf::from("input.txt"), answer: String::from("a,b c") },
+        ];
+        assert_eq!(Ok(expected), parse_manifest(manifest));
+    }
+
+    #[test]
+    fn test_parse_invalid_manifest() {
+        assert_eq!(Err(String::from("Line 2: expected '<day> <part> <input file> <answer>', found '1 1 day1.txt'")),
+                   parse_manifest("1 1 day1.txt 5\n1 1 day1.txt"));
+        assert_eq!(Err(String::from("Line 1: invalid part 'x'")), parse_manifest("1 x day1.txt 5"));
+    }
+
+    #[test]
+    fn test_compare() {
+        let expectation = Expectation { day: 1, part: 1, input: PathBuf::from("day1.txt"), answer: String::from("5") };
+        assert_eq!(Outcome::Correct, expectation.compare(Ok(String::from("5"))));
+        assert_eq!(Outcome::Wrong { actual: String::from("6") }, expectation.compare(Ok(String::from("6"))));
+        assert_eq!(Outcome::Failed { message: String::from("oops") }, expectation.compare(Err(String::from("oops"))));
+    }
+
+    #[test]
+    fn test_verify_missing_input() {
+        let expectation = Expectation { day: 1, part: 1, input: PathBuf::from("resources/missing.txt"), answer: String::from("5") };
+        assert!(matches!(expectation.verify(), Outcome::Failed { .. }));
+    }
+}
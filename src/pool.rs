@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+/// Applies `f` to all items on `jobs` worker threads and passes the results to `consume` in the order of the items.
+/// A result is passed on as soon as the results of all items before it are available
+pub fn map_in_order<T, R>(items: Vec<T>, jobs: usize, f: impl Fn(T) -> R + Sync, mut consume: impl FnMut(R))
+    where T: Send, R: Send {
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                // the lock is released before the item is processed
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some((idx, item)) => {
+                        if sender.send((idx, f(item))).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+        // the workers hold the remaining senders, so the loop ends once they are done
+        drop(sender);
+        let mut pending = HashMap::new();
+        let mut next_idx = 0;
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&next_idx) {
+                consume(result);
+                next_idx += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_results_in_order() {
+        for &jobs in &[1, 3, 20] {
+            let mut results = Vec::new();
+            map_in_order((1..=10).collect(), jobs, |n: u64| {
+                thread::sleep(std::time::Duration::from_millis(10 - n));
+                n * n
+            }, |n| results.push(n));
+            assert_eq!(vec![1, 4, 9, 16, 25, 36, 49, 64, 81, 100], results);
+        }
+    }
+
+    #[test]
+    fn test_items_run_in_parallel() {
+        // would never finish if the items were processed one after another
+        let barrier = Barrier::new(3);
+        let mut count = 0;
+        map_in_order(vec![1, 2, 3], 3, |_| { barrier.wait(); }, |_| count += 1);
+        assert_eq!(3, count);
+    }
+
+    #[test]
+    fn test_no_items() {
+        map_in_order(Vec::<u8>::new(), 4, |n| n, |_| panic!("no results expected"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::error::AocError;
+use crate::solver::{Answer, Solver};
+use crate::*;
+
+type ParseFn = fn(&str) -> Result<Box<dyn Solver>, AocError>;
+
+/// Entry of the registry: knows how to parse the input of a day into its solver
+#[derive(Clone, Copy)]
+pub struct Registration {
+    pub day: u8,
+    pub parts: &'static [u8],
+    parse: ParseFn,
+}
+
+impl Registration {
+    fn new<S: Solver + 'static>(day: u8) -> Registration {
+        Registration {
+            day,
+            parts: S::parts(),
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    /// Normalizes the input and parses it
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solver>, AocError> {
+        (self.parse)(&input::normalize(input))
+    }
+}
+
+fn parse_boxed<S: Solver + 'static>(input: &str) -> Result<Box<dyn Solver>, AocError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+/// Returns the registrations of all days, ordered by day
+pub fn get_registrations() -> Vec<Registration> {
+    vec![
+        Registration::new::<day1::Day1>(1),
+        Registration::new::<day2::Day2>(2),
+        Registration::new::<day3::Day3>(3),
+        Registration::new::<day4::Day4>(4),
+        Registration::new::<day5::Day5>(5),
+        Registration::new::<day6::Day6>(6),
+        Registration::new::<day7::Day7>(7),
+        Registration::new::<day8::Day8>(8),
+        Registration::new::<day9::Day9>(9),
+        Registration::new::<day10::Day10>(10),
+        Registration::new::<day11::Day11>(11),
+        Registration::new::<day12::Day12>(12),
+        Registration::new::<day13::Day13>(13),
+        Registration::new::<day14::Day14>(14),
+        Registration::new::<day15::Day15>(15),
+        Registration::new::<day16::Day16>(16),
+        Registration::new::<day17b::Day17>(17),
+        Registration::new::<day18::Day18>(18),
+        Registration::new::<day19::Day19>(19),
+        Registration::new::<day20::Day20>(20),
+        Registration::new::<day21::Day21>(21),
+        Registration::new::<day22::Day22>(22),
+        Registration::new::<day23::Day23>(23),
+        Registration::new::<day24::Day24>(24),
+        Registration::new::<day25::Day25>(25),
+    ]
+}
+
+pub fn get_registration(day: u8) -> Option<Registration> {
+    get_registrations().into_iter().find(|registration| registration.day == day)
+}
+
+/// Parses the input and solves a single part of a day
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer, AocError> {
+    let registration = get_registration(day).ok_or(AocError::UnknownDay { day })?;
+    if !registration.parts.contains(&part) {
+        return Err(AocError::UnknownPart { day, part });
+    }
+    registration.parse(input)?.solve(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_days_registered() {
+        let days: Vec<u8> = get_registrations().iter().map(|registration| registration.day).collect();
+        assert_eq!((1..=25).collect::<Vec<u8>>(), days);
+    }
+
+    #[test]
+    fn test_get_registration() {
+        assert_eq!(&[1, 2], get_registration(1).unwrap().parts);
+        assert_eq!(&[1], get_registration(25).unwrap().parts);
+        assert!(get_registration(26).is_none());
+    }
+
+    #[test]
+    fn test_solve_examples() {
+        assert_eq!(Answer::Integer(514579), solve(1, 1, "1721\n979\n366\n299\n675\n1456").unwrap());
+        assert_eq!(Answer::Integer(295), solve(13, 1, "939\n7,13,x,x,59,x,31,19\n").unwrap());
+        assert_eq!(Answer::Integer(436), solve(15, 1, "0,3,6\n").unwrap());
+        assert_eq!(Answer::from("67384529"), solve(23, 1, "389125467\n").unwrap());
+        assert_eq!(Answer::Integer(14897079), solve(25, 1, "5764801\n17807724\n").unwrap());
+    }
+
+    #[test]
+    fn test_solve_windows_line_endings() {
+        let input = "\u{feff}ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\r\nbyr:1937 iyr:2017 cid:147 hgt:183cm \r\n\r\n\
+                     iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\r\nhcl:#cfa07d byr:1929\r\n";
+        assert_eq!(Answer::Integer(1), solve(4, 1, input).unwrap());
+        assert_eq!(Answer::Integer(14), solve(22, 1, "Player 1:\r\n2\r\n3\r\n\r\nPlayer 2:\r\n1\r\n").unwrap());
+    }
+
+    #[test]
+    fn test_solve_unknown_part() {
+        assert_eq!(Err(AocError::UnknownPart { day: 25, part: 2 }), solve(25, 2, "5764801\n17807724\n"));
+        assert_eq!(Err(AocError::UnknownDay { day: 26 }), solve(26, 1, ""));
+    }
+}
@@ -0,0 +1,136 @@
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::solver::Answer;
+
+/// How the runner reports its results
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("Unknown format: '{}' (expected text, json or csv)", format)),
+        }
+    }
+}
+
+/// Result of solving one part, including the time it took to parse the input and solve it, and the wall time of the
+/// whole day, which includes reading the input and solving the other parts
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub result: Result<Answer, String>,
+    pub elapsed: Duration,
+    pub day_elapsed: Duration,
+}
+
+/// Flat representation of a `PartReport` for JSON and CSV output. Either `answer` or `error` is set
+#[derive(Debug, Serialize)]
+struct PartRecord<'a> {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    answer_type: Option<&'static str>,
+    elapsed_ns: u64,
+    day_elapsed_ns: u64,
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a PartReport> for PartRecord<'a> {
+    fn from(report: &'a PartReport) -> Self {
+        PartRecord {
+            day: report.day,
+            part: report.part,
+            answer: report.result.as_ref().ok().map(|answer| answer.to_string()),
+            answer_type: report.result.as_ref().ok().map(|answer| answer.type_name()),
+            elapsed_ns: report.elapsed.as_nanos() as u64,
+            day_elapsed_ns: report.day_elapsed.as_nanos() as u64,
+            error: report.result.as_ref().err().map(|e| e.as_str()),
+        }
+    }
+}
+
+pub fn write_json(reports: &[PartReport], writer: impl Write) -> Result<(), String> {
+    let records: Vec<PartRecord> = reports.iter().map(PartRecord::from).collect();
+    write_json_records(&records, writer)
+}
+
+pub fn write_csv(reports: &[PartReport], writer: impl Write) -> Result<(), String> {
+    let records: Vec<PartRecord> = reports.iter().map(PartRecord::from).collect();
+    write_csv_records(&records, writer)
+}
+
+/// Writes the records as a pretty-printed JSON array
+pub fn write_json_records<T: Serialize>(records: &[T], writer: impl Write) -> Result<(), String> {
+    serde_json::to_writer_pretty(writer, records).map_err(|e| e.to_string())
+}
+
+/// Writes the records as CSV with a header taken from the field names of the first record
+pub fn write_csv_records<T: Serialize>(records: &[T], writer: impl Write) -> Result<(), String> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for record in records {
+        writer.serialize(record).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_reports() -> Vec<PartReport> {
+        vec![
+            PartReport { day: 13, part: 1, result: Ok(Answer::Integer(295)), elapsed: Duration::from_nanos(1500),
+                day_elapsed: Duration::from_nanos(4000) },
+            PartReport { day: 21, part: 2, result: Ok(Answer::from("a,b")), elapsed: Duration::from_nanos(20),
+                day_elapsed: Duration::from_nanos(50) },
+            PartReport { day: 25, part: 2, result: Err(String::from("Day 25 has no part 2")), elapsed: Duration::from_nanos(3),
+                day_elapsed: Duration::from_nanos(3) },
+        ]
+    }
+
+    #[test]
+    fn test_report_format() {
+        assert_eq!(Ok(ReportFormat::Json), "json".parse());
+        assert_eq!(Ok(ReportFormat::Text), "text".parse());
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut output = Vec::new();
+        write_csv(&get_reports(), &mut output).unwrap();
+        assert_eq!("day,part,answer,answer_type,elapsed_ns,day_elapsed_ns,error\n\
+                    13,1,295,integer,1500,4000,\n\
+                    21,2,\"a,b\",text,20,50,\n\
+                    25,2,,,3,3,Day 25 has no part 2\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut output = Vec::new();
+        write_json(&get_reports(), &mut output).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!("295", json[0]["answer"]);
+        assert_eq!("integer", json[0]["answer_type"]);
+        assert_eq!(1500, json[0]["elapsed_ns"]);
+        assert_eq!(4000, json[0]["day_elapsed_ns"]);
+        assert!(json[0]["error"].is_null());
+        assert!(json[2]["answer"].is_null());
+        assert_eq!("Day 25 has no part 2", json[2]["error"]);
+    }
+}
@@ -0,0 +1,253 @@
+use std::error::Error;
+use std::io::Read;
+use std::panic;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc2020::bench::{bench, write_results};
+use aoc2020::error::AocError;
+use aoc2020::input::{normalize, read_file};
+use aoc2020::manifest::{read_manifest, Outcome};
+use aoc2020::pool;
+use aoc2020::registry::{get_registration, solve};
+use aoc2020::report::{self, PartReport, ReportFormat};
+use aoc2020::solver::Answer;
+
+use crate::cli::{BenchOptions, Input, RunOptions, VerifyOptions};
+
+pub fn read_input(day: u8, input: &Option<Input>) -> Result<String, Box<dyn Error>> {
+    match input {
+        None => Ok(read_file(Path::new(&format!("resources/day{}.txt", day)))?),
+        Some(Input::File(path)) => Ok(read_file(path)?),
+        Some(Input::Stdin) => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(normalize(&input))
+        }
+    }
+}
+
+/// Solves a single part, turning panics of the solver into errors so that a single broken day does not abort the whole run
+fn solve_catching_panics(day: u8, part: u8, input: &str) -> Result<Answer, String> {
+    catch_panics(day, || solve(day, part, input).map_err(|e| e.to_string()))
+}
+
+fn catch_panics<T>(day: u8, f: impl FnOnce() -> Result<T, String> + panic::UnwindSafe) -> Result<T, String> {
+    match panic::catch_unwind(f) {
+        Ok(result) => result,
+        Err(payload) => Err(match payload.downcast_ref::<&str>() {
+            Some(message) => format!("Day {}: solver panicked: {}", day, message),
+            None => match payload.downcast_ref::<String>() {
+                Some(message) => format!("Day {}: solver panicked: {}", day, message),
+                None => format!("Day {}: solver panicked", day),
+            },
+        }),
+    }
+}
+
+/// Answers of the selected parts of one day, and the wall time it took to read the input and solve them
+struct DayRun {
+    day: u8,
+    reports: Vec<PartReport>,
+    elapsed: Duration,
+}
+
+fn run_day(day: u8, options: &RunOptions) -> DayRun {
+    let start = Instant::now();
+    let mut reports: Vec<PartReport> = match select_parts(day, options.part) {
+        Ok(parts) => {
+            let input = read_input(day, &options.input);
+            parts.into_iter()
+                .map(|part| match &input {
+                    Ok(input) => {
+                        let start = Instant::now();
+                        let result = solve_catching_panics(day, part, input);
+                        PartReport { day, part, result, elapsed: start.elapsed(), day_elapsed: Duration::default() }
+                    }
+                    Err(e) => PartReport { day, part, result: Err(format!("Day {}: {}", day, e)), elapsed: Duration::default(),
+                        day_elapsed: Duration::default() },
+                })
+                .collect()
+        }
+        // without a registration, the error is reported for both parts a puzzle usually has
+        Err(e) => options.part.map_or(vec![1, 2], |part| vec![part]).into_iter()
+            .map(|part| PartReport { day, part, result: Err(e.to_string()), elapsed: Duration::default(),
+                day_elapsed: Duration::default() })
+            .collect(),
+    };
+    let elapsed = start.elapsed();
+    reports.iter_mut().for_each(|report| report.day_elapsed = elapsed);
+    DayRun { day, reports, elapsed }
+}
+
+/// Returns the requested part, or all parts of the day if none is requested
+fn select_parts(day: u8, part: Option<u8>) -> Result<Vec<u8>, AocError> {
+    let registration = get_registration(day).ok_or(AocError::UnknownDay { day })?;
+    match part {
+        Some(part) if !registration.parts.contains(&part) => Err(AocError::UnknownPart { day, part }),
+        Some(part) => Ok(vec![part]),
+        None => Ok(registration.parts.to_vec()),
+    }
+}
+
+/// Runs all selected days and parts on `options.jobs` threads and returns the number of failures
+pub fn run(options: &RunOptions) -> usize {
+    let start = Instant::now();
+    let mut reports = Vec::new();
+    pool::map_in_order(options.days.clone(), options.jobs, |day| run_day(day, options), |day_run| {
+        if options.format == ReportFormat::Text {
+            day_run.reports.iter().for_each(print_report);
+            println!("Day {} took {:.2?}", day_run.day, day_run.elapsed);
+        }
+        reports.extend(day_run.reports);
+    });
+    if options.format == ReportFormat::Text && options.days.len() > 1 {
+        println!("Solved {} days in {:.2?}", options.days.len(), start.elapsed());
+    }
+    let mut failures = reports.iter().filter(|report| report.result.is_err()).count();
+    let written = match options.format {
+        ReportFormat::Text => Ok(()),
+        ReportFormat::Json => report::write_json(&reports, std::io::stdout()).map(|_| println!()),
+        ReportFormat::Csv => report::write_csv(&reports, std::io::stdout()),
+    };
+    if let Err(e) = written {
+        eprintln!("Error writing results: {}", e);
+        failures += 1;
+    }
+    failures
+}
+
+fn print_report(report: &PartReport) {
+    match &report.result {
+        Ok(answer) => println!("Day {} part {}: {}", report.day, report.part, answer),
+        // the message already names the day
+        Err(e) => eprintln!("Error in part {}: {}", report.part, e),
+    }
+}
+
+/// Checks the selected days and parts against the manifest and returns the number of wrong or failed answers
+pub fn verify(options: &VerifyOptions) -> usize {
+    let expectations = match read_manifest(&options.manifest) {
+        Ok(expectations) => expectations,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let expectations: Vec<_> = expectations.into_iter()
+        .filter(|e| options.day.is_none() || options.day == Some(e.day))
+        .filter(|e| options.part.is_none() || options.part == Some(e.part))
+        .collect();
+    if expectations.is_empty() {
+        eprintln!("{} has no answers for the selected days", options.manifest.display());
+        return 1;
+    }
+    let mut failures = 0;
+    for expectation in &expectations {
+        let outcome = catch_panics(expectation.day, || Ok(expectation.verify()))
+            .unwrap_or_else(|message| Outcome::Failed { message });
+        match outcome {
+            Outcome::Correct => println!("Day {} part {}: {}", expectation.day, expectation.part, outcome),
+            Outcome::Wrong { .. } => {
+                eprintln!("Day {} part {}: {}, expected {}", expectation.day, expectation.part, outcome, expectation.answer);
+                failures += 1;
+            }
+            Outcome::Failed { .. } => {
+                eprintln!("Day {} part {}: {}", expectation.day, expectation.part, outcome);
+                failures += 1;
+            }
+        }
+    }
+    println!("{} of {} answers correct", expectations.len() - failures, expectations.len());
+    failures
+}
+
+/// Measures parse and solve times of the selected days and parts and returns the number of failures
+pub fn run_benchmarks(options: &BenchOptions) -> usize {
+    let mut failures = 0;
+    let mut results = Vec::new();
+    for &day in &options.days {
+        let parts = match get_registration(day) {
+            Some(registration) => registration.parts,
+            None => {
+                eprintln!("{}", AocError::UnknownDay { day });
+                failures += 1;
+                continue;
+            }
+        };
+        if let Some(part) = options.part.filter(|part| !parts.contains(part)) {
+            eprintln!("{}", AocError::UnknownPart { day, part });
+            failures += 1;
+            continue;
+        }
+        let input = match read_input(day, &None) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e);
+                failures += 1;
+                continue;
+            }
+        };
+        for &part in parts {
+            if options.part.is_some() && options.part != Some(part) {
+                continue;
+            }
+            match catch_panics(day, || bench(day, part, &input, options.runs).map_err(|e| e.to_string())) {
+                Ok(result) => {
+                    println!("Day {} part {}: parse {:.2?} / {:.2?} / {:.2?}, solve {:.2?} / {:.2?} / {:.2?} (min / median / max of {} runs)",
+                             day, part, result.parse.min, result.parse.median, result.parse.max,
+                             result.solve.min, result.solve.median, result.solve.max, result.runs);
+                    results.push(result);
+                }
+                Err(e) => {
+                    eprintln!("Error in part {}: {}", part, e);
+                    failures += 1;
+                }
+            }
+        }
+    }
+    if let Some(output) = &options.output {
+        if let Err(e) = write_results(&results, output) {
+            eprintln!("{}", e);
+            failures += 1;
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_part_is_an_error() {
+        let options = RunOptions { days: vec![25], part: Some(2), input: None, format: ReportFormat::Text, jobs: 1 };
+        let day_run = run_day(25, &options);
+        assert_eq!(vec![PartReport { day: 25, part: 2, result: Err(String::from("Day 25 has no part 2")), elapsed: Duration::default(),
+                                     day_elapsed: day_run.elapsed }],
+                   day_run.reports);
+        assert_eq!(1, run(&options));
+    }
+
+    #[test]
+    fn test_unknown_day_is_an_error() {
+        let options = RunOptions { days: vec![26], part: None, input: None, format: ReportFormat::Json, jobs: 1 };
+        let reports = run_day(26, &options).reports;
+        assert_eq!(vec![(1, Err(String::from("Day 26 is not solved yet"))), (2, Err(String::from("Day 26 is not solved yet")))],
+                   reports.into_iter().map(|report| (report.part, report.result)).collect::<Vec<_>>());
+        assert_eq!(2, run(&options));
+    }
+
+    #[test]
+    fn test_parse_error_is_reported() {
+        assert_eq!(Err(String::from("Day 8, line 1, column 1: Unknown operation 'this'")),
+                   solve_catching_panics(8, 1, "this is not a programme"));
+    }
+
+    #[test]
+    fn test_solver_panic_is_an_error() {
+        assert_eq!(Err(String::from("Day 1: solver panicked: boom")), catch_panics(1, || -> Result<(), String> { panic!("boom") }));
+        assert_eq!(Err(String::from("Day 2: solver panicked: 42")), catch_panics(2, || -> Result<(), String> { panic!("{}", 42) }));
+        assert_eq!(Ok(3), catch_panics(3, || Ok(3)));
+    }
+}
@@ -0,0 +1,109 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error::AocError;
+
+/// The answer to one part of a puzzle
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// Name of the kind of answer, as used in machine-readable output
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+/// Common interface of all days: the input is parsed once, after which both parts can be solved independently
+pub trait Solver {
+    fn parse(input: &str) -> Result<Self, AocError> where Self: Sized;
+
+    fn part1(&self) -> Result<Answer, AocError>;
+
+    fn part2(&self) -> Result<Answer, AocError>;
+
+    fn day(&self) -> u8;
+
+    /// The parts this day consists of
+    fn parts() -> &'static [u8] where Self: Sized {
+        &[1, 2]
+    }
+
+    fn solve(&self, part: u8) -> Result<Answer, AocError> {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => Err(AocError::UnknownPart { day: self.day(), part }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::Integer(42), Answer::from(42_usize));
+        assert_eq!(Answer::Integer(-42), Answer::from(-42_i64));
+        assert_eq!(Answer::Integer(264692662390), Answer::from(264692662390_u128));
+        assert_eq!(Answer::Text(u128::MAX.to_string()), Answer::from(u128::MAX));
+        assert_eq!(Answer::Text(String::from("abc")), Answer::from("abc"));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("-42", Answer::Integer(-42).to_string());
+        assert_eq!("mxmxvkd,sqjhc,fvjkl", Answer::from("mxmxvkd,sqjhc,fvjkl").to_string());
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use aoc2020::manifest::{read_manifest, Expectation, Outcome, DEFAULT_MANIFEST};
+use aoc2020::registry::get_registrations;
+
+/// Days that take more than a few seconds in debug builds
+const SLOW_DAYS: [u8; 4] = [15, 17, 20, 22];
+
+fn get_expectations() -> Vec<Expectation> {
+    read_manifest(Path::new(DEFAULT_MANIFEST)).unwrap()
+}
+
+fn assert_all_correct<'a>(expectations: impl Iterator<Item=&'a Expectation>) {
+    let failures: Vec<String> = expectations
+        .map(|e| (e, e.verify()))
+        .filter(|(_, outcome)| *outcome != Outcome::Correct)
+        .map(|(e, outcome)| format!("day {} part {}: {}, expected {}", e.day, e.part, outcome, e.answer))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_manifest_covers_all_parts() {
+    let expectations = get_expectations();
+    for registration in get_registrations() {
+        for &part in registration.parts {
+            assert!(expectations.iter().any(|e| e.day == registration.day && e.part == part),
+                    "No expected answer for day {} part {}", registration.day, part);
+        }
+    }
+}
+
+#[test]
+fn test_answers() {
+    assert_all_correct(get_expectations().iter().filter(|e| !SLOW_DAYS.contains(&e.day)));
+}
+
+#[test]
+#[ignore] // takes a long time (quicker to run after cargo build --release)
+fn test_answers_of_slow_days() {
+    assert_all_correct(get_expectations().iter().filter(|e| SLOW_DAYS.contains(&e.day)));
+}
@@ -0,0 +1,45 @@
+use aoc2020::day18::interpreter::Interpreter;
+use aoc2020::day18::lexer::Lexer;
+use aoc2020::grid::Grid;
+use aoc2020::registry::{get_registrations, solve};
+use aoc2020::solver::{Answer, Solver};
+
+#[test]
+fn test_solve_through_registry() {
+    let input = "1721\n979\n366\n299\n675\n1456";
+    assert_eq!(Answer::Integer(514579), solve(1, 1, input).unwrap());
+    assert_eq!(Answer::Integer(241861950), solve(1, 2, input).unwrap());
+}
+
+#[test]
+fn test_parse_once_solve_twice() {
+    let input = "939\n7,13,x,x,59,x,31,19\n";
+    let registration = get_registrations().into_iter().find(|r| r.day == 13).unwrap();
+    let solver = registration.parse(input).unwrap();
+    assert_eq!(Answer::Integer(295), solver.solve(1).unwrap());
+    assert_eq!(Answer::Integer(1068781), solver.solve(2).unwrap());
+}
+
+#[test]
+fn test_use_day_solver_directly() {
+    let solver = aoc2020::day23::Day23::parse("389125467").unwrap();
+    assert_eq!(Answer::from("67384529"), solver.part1().unwrap());
+}
+
+#[test]
+fn test_rotate_and_flip() {
+    let grid = Grid::new(2, vec![1, 2,
+                                 3, 4]);
+    assert_eq!(Grid::new(2, vec![3, 1,
+                                 4, 2]), grid.rotate());
+    assert_eq!(Grid::new(2, vec![2, 1,
+                                 4, 3]), grid.flip_horizontal());
+    assert_eq!(Grid::new(2, vec![3, 4,
+                                 1, 2]), grid.flip_vertical());
+}
+
+#[test]
+fn test_interpreter() {
+    assert_eq!(Ok(26), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), true).term());
+    assert_eq!(Ok(46), Interpreter::new(Lexer::new("2 * 3 + (4 * 5)"), false).term());
+}